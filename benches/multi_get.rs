@@ -0,0 +1,34 @@
+use ckb_rocksdb::{TemporaryDBPath, prelude::*};
+use criterion::{BenchmarkId, Criterion, criterion_group, criterion_main};
+
+const BIG_VALUE: [u8; 1024] = [0u8; 1024];
+const NUM: u64 = 10000;
+
+pub fn bench(c: &mut Criterion) {
+    let mut group = c.benchmark_group("multi_get");
+
+    let path = TemporaryDBPath::new();
+    let db = DB::open_default(&path).unwrap();
+    for i in 0..NUM {
+        db.put(i.to_le_bytes(), &BIG_VALUE[..]).unwrap();
+    }
+    db.flush().unwrap();
+    let keys: Vec<_> = (0..NUM).map(u64::to_le_bytes).collect();
+
+    group.bench_with_input(BenchmarkId::new("serial", NUM), &keys, |b, keys| {
+        b.iter(|| db.multi_get(keys))
+    });
+
+    for num_threads in [2, 4, 8] {
+        group.bench_with_input(
+            BenchmarkId::new(format!("parallel {} threads", num_threads), NUM),
+            &keys,
+            |b, keys| b.iter(|| db.multi_get_parallel(keys, num_threads)),
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench);
+criterion_main!(benches);
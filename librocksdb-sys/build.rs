@@ -163,9 +163,11 @@ fn build_rocksdb() {
 
     config.include(".");
     config.define("NDEBUG", Some("1"));
-    // Explicitly disable stats and perf
-    config.define("NIOSTATS_CONTEXT", None);
-    config.define("NPERF_CONTEXT", None);
+    // Explicitly disable stats and perf unless they are asked for
+    if !cfg!(feature = "perf") {
+        config.define("NIOSTATS_CONTEXT", None);
+        config.define("NPERF_CONTEXT", None);
+    }
 
     let mut lib_sources = include_str!("rocksdb_lib_sources.txt")
         .trim()
@@ -1,12 +1,64 @@
 #include "patches/rocksdb.h"
 
+#include <cassert>
+#include <cstring>
+#include <string>
+#include <unordered_map>
+#include <vector>
+
+#include "rocksdb/compaction_filter.h"
+#include "rocksdb/db.h"
+#include "rocksdb/experimental.h"
+#include "rocksdb/listener.h"
+#include "rocksdb/options.h"
+#include "rocksdb/sst_file_manager.h"
+#include "rocksdb/statistics.h"
+#include "rocksdb/table_properties.h"
+#include "rocksdb/utilities/debug.h"
 #include "rocksdb/utilities/options_util.h"
+#include "rocksdb/utilities/transaction.h"
 
 using rocksdb::Cache;
 using rocksdb::ColumnFamilyDescriptor;
+using rocksdb::ColumnFamilyHandle;
+using rocksdb::CompactionFilter;
+using rocksdb::CompactionFilterFactory;
+using rocksdb::CompactionOptions;
+using rocksdb::CompactionJobInfo;
+using rocksdb::CompressionType;
+using rocksdb::DB;
 using rocksdb::Env;
+using rocksdb::EventListener;
+using rocksdb::FlushJobInfo;
+using rocksdb::GetMergeOperandsOptions;
+using rocksdb::IngestExternalFileOptions;
+using rocksdb::KeyVersion;
 using rocksdb::Options;
+using rocksdb::PinnableSlice;
+using rocksdb::ReadOptions;
+using rocksdb::SequenceNumber;
+using rocksdb::SstFileManager;
+using rocksdb::Slice;
+using rocksdb::Statistics;
 using rocksdb::Status;
+using rocksdb::TableProperties;
+using rocksdb::TablePropertiesCollector;
+using rocksdb::TablePropertiesCollectorFactory;
+using rocksdb::Transaction;
+using rocksdb::UserCollectedProperties;
+
+static bool SaveError(char** errptr, const Status& s) {
+    assert(errptr != nullptr);
+    if (s.ok()) {
+        return false;
+    } else if (*errptr == nullptr) {
+        *errptr = strdup(s.ToString().c_str());
+    } else {
+        free(*errptr);
+        *errptr = strdup(s.ToString().c_str());
+    }
+    return true;
+}
 
 extern "C" {
     // Copy structs from librocksdb-sys/rocksdb/db/c.cc
@@ -20,6 +72,33 @@ extern "C" {
     struct rocksdb_options_t {
         Options rep;
     };
+    struct rocksdb_t {
+        DB* rep;
+    };
+    struct rocksdb_column_family_handle_t {
+        ColumnFamilyHandle* rep;
+        bool immortal; /* only true for default cf */
+    };
+    struct rocksdb_readoptions_t {
+        ReadOptions rep;
+        // stack variables to set pointers to in ReadOptions
+        Slice upper_bound;
+        Slice lower_bound;
+        Slice timestamp;
+        Slice iter_start_ts;
+    };
+    struct rocksdb_transaction_t {
+        Transaction* rep;
+    };
+    struct rocksdb_ingestexternalfileoptions_t {
+        IngestExternalFileOptions rep;
+    };
+    struct rocksdb_compactionfiltercontext_t {
+        CompactionFilter::Context rep;
+    };
+    struct rocksdb_snapshot_t {
+        const rocksdb::Snapshot* rep;
+    };
 
     // New structs
     struct rocksdb_column_family_descriptor_t {
@@ -29,6 +108,186 @@ extern "C" {
     struct rocksdb_column_family_descriptors_t {
         std::vector<rocksdb_column_family_descriptor_t> rep;
     };
+    struct rocksdb_compactionoptions_t {
+        CompactionOptions rep;
+    };
+    struct rocksdb_sstfilemanager_t {
+        std::shared_ptr<SstFileManager> rep;
+    };
+    struct rocksdb_compactionfilterv2_t : public CompactionFilter {
+        void* state_;
+        void (*destructor_)(void*);
+        unsigned char (*filter_)(
+            void*,
+            int level,
+            const char* key,
+            size_t key_length,
+            const char* existing_value,
+            size_t value_length,
+            char** new_value,
+            size_t* new_value_length,
+            unsigned char* value_changed,
+            char** skip_until,
+            size_t* skip_until_length);
+        const char* (*name_)(void*);
+
+        ~rocksdb_compactionfilterv2_t() override { (*destructor_)(state_); }
+
+        Decision FilterV2(
+            int level,
+            const Slice& key,
+            ValueType value_type,
+            const Slice& existing_value,
+            std::string* new_value,
+            std::string* skip_until) const override {
+            if (value_type != ValueType::kValue) {
+                return CompactionFilter::FilterV2(
+                    level, key, value_type, existing_value, new_value, skip_until);
+            }
+            char* c_new_value = nullptr;
+            size_t new_value_length = 0;
+            unsigned char c_value_changed = 0;
+            char* c_skip_until = nullptr;
+            size_t skip_until_length = 0;
+            unsigned char result = (*filter_)(
+                state_,
+                level,
+                key.data(), key.size(),
+                existing_value.data(), existing_value.size(),
+                &c_new_value, &new_value_length, &c_value_changed,
+                &c_skip_until, &skip_until_length);
+            switch (result) {
+                case 1:
+                    return Decision::kRemove;
+                case 2:
+                    skip_until->assign(c_skip_until, skip_until_length);
+                    return Decision::kRemoveAndSkipUntil;
+                default:
+                    if (c_value_changed) {
+                        new_value->assign(c_new_value, new_value_length);
+                        return Decision::kChangeValue;
+                    }
+                    return Decision::kKeep;
+            }
+        }
+
+        const char* Name() const override { return (*name_)(state_); }
+    };
+    struct rocksdb_compactionfilterfactoryv2_t : public CompactionFilterFactory {
+        void* state_;
+        void (*destructor_)(void*);
+        rocksdb_compactionfilterv2_t* (*create_compaction_filter_)(
+            void*, rocksdb_compactionfiltercontext_t* context);
+        const char* (*name_)(void*);
+
+        ~rocksdb_compactionfilterfactoryv2_t() override { (*destructor_)(state_); }
+
+        std::unique_ptr<CompactionFilter> CreateCompactionFilter(
+            const CompactionFilter::Context& context) override {
+            rocksdb_compactionfiltercontext_t ccontext;
+            ccontext.rep = context;
+            CompactionFilter* cf = (*create_compaction_filter_)(state_, &ccontext);
+            return std::unique_ptr<CompactionFilter>(cf);
+        }
+
+        const char* Name() const override { return (*name_)(state_); }
+    };
+    struct rocksdb_eventlistener_t : public EventListener {
+        void* state_;
+        void (*destructor_)(void*);
+        void (*on_flush_completed_)(
+            void*,
+            const char* cf_name,
+            size_t cf_name_length,
+            const char* file_path,
+            size_t file_path_length,
+            uint64_t smallest_seqno,
+            uint64_t largest_seqno);
+        void (*on_compaction_completed_)(
+            void*,
+            const char* cf_name,
+            size_t cf_name_length,
+            const char* errmsg,
+            int base_input_level,
+            int output_level,
+            size_t num_input_files,
+            size_t num_output_files);
+
+        ~rocksdb_eventlistener_t() override { (*destructor_)(state_); }
+
+        void OnFlushCompleted(DB*, const FlushJobInfo& info) override {
+            (*on_flush_completed_)(
+                state_,
+                info.cf_name.data(), info.cf_name.size(),
+                info.file_path.data(), info.file_path.size(),
+                info.smallest_seqno, info.largest_seqno);
+        }
+
+        void OnCompactionCompleted(DB*, const CompactionJobInfo& info) override {
+            std::string errmsg = info.status.ToString();
+            (*on_compaction_completed_)(
+                state_,
+                info.cf_name.data(), info.cf_name.size(),
+                info.status.ok() ? nullptr : errmsg.c_str(),
+                info.base_input_level, info.output_level,
+                info.input_files.size(), info.output_files.size());
+        }
+    };
+
+    struct rocksdb_tablepropertiescollector_t : public TablePropertiesCollector {
+        void* state_;
+        void (*destructor_)(void*);
+        void (*add_)(
+            void*,
+            const char* key,
+            size_t key_length,
+            const char* value,
+            size_t value_length);
+        void (*finish_)(void*, rocksdb_usercollectedproperties_t* props);
+        const char* (*name_)(void*);
+
+        ~rocksdb_tablepropertiescollector_t() override { (*destructor_)(state_); }
+
+        Status AddUserKey(
+            const Slice& key,
+            const Slice& value,
+            rocksdb::EntryType type,
+            SequenceNumber,
+            uint64_t) override {
+            if (type == rocksdb::kEntryPut) {
+                (*add_)(state_, key.data(), key.size(), value.data(), value.size());
+            }
+            return Status::OK();
+        }
+
+        Status Finish(UserCollectedProperties* properties) override {
+            (*finish_)(state_, reinterpret_cast<rocksdb_usercollectedproperties_t*>(properties));
+            return Status::OK();
+        }
+
+        UserCollectedProperties GetReadableProperties() const override {
+            return UserCollectedProperties();
+        }
+
+        const char* Name() const override { return (*name_)(state_); }
+    };
+
+    struct rocksdb_tablepropertiescollectorfactory_t : public TablePropertiesCollectorFactory {
+        void* state_;
+        void (*destructor_)(void*);
+        rocksdb_tablepropertiescollector_t* (*create_collector_)(
+            void*, uint32_t column_family_id);
+        const char* (*name_)(void*);
+
+        ~rocksdb_tablepropertiescollectorfactory_t() override { (*destructor_)(state_); }
+
+        TablePropertiesCollector* CreateTablePropertiesCollector(
+            TablePropertiesCollectorFactory::Context context) override {
+            return (*create_collector_)(state_, context.column_family_id);
+        }
+
+        const char* Name() const override { return (*name_)(state_); }
+    };
 
     rocksdb_cache_t* rocksdb_null_cache() {
         rocksdb_cache_t* c = new rocksdb_cache_t;
@@ -42,6 +301,10 @@ extern "C" {
         return o;
     }
 
+    void rocksdb_options_set_two_write_queues(rocksdb_options_t* opt, unsigned char v) {
+        opt->rep.two_write_queues = v;
+    }
+
     rocksdb_column_family_descriptors_t* rocksdb_column_family_descriptors_create() {
         return new rocksdb_column_family_descriptors_t;
     }
@@ -114,4 +377,508 @@ extern "C" {
         *errptr = strdup(status.ToString().c_str());
         return full_opts;
     }
+
+    rocksdb_compactionoptions_t* rocksdb_compactionoptions_create() {
+        return new rocksdb_compactionoptions_t;
+    }
+
+    void rocksdb_compactionoptions_destroy(rocksdb_compactionoptions_t* opt) {
+        delete opt;
+    }
+
+    void rocksdb_compactionoptions_set_compression(rocksdb_compactionoptions_t* opt, int compression) {
+        opt->rep.compression = static_cast<CompressionType>(compression);
+    }
+
+    void rocksdb_compactionoptions_set_output_file_size_limit(rocksdb_compactionoptions_t* opt, uint64_t limit) {
+        opt->rep.output_file_size_limit = limit;
+    }
+
+    void rocksdb_compactionoptions_set_max_subcompactions(rocksdb_compactionoptions_t* opt, uint32_t max) {
+        opt->rep.max_subcompactions = max;
+    }
+
+    void rocksdb_compact_files(
+        rocksdb_t* db,
+        const rocksdb_compactionoptions_t* opt,
+        const char* const* input_file_names,
+        size_t num_input_files,
+        int output_level,
+        char** errptr) {
+        std::vector<std::string> names(input_file_names, input_file_names + num_input_files);
+        SaveError(errptr, db->rep->CompactFiles(opt->rep, names, output_level));
+    }
+
+    void rocksdb_compact_files_cf(
+        rocksdb_t* db,
+        const rocksdb_compactionoptions_t* opt,
+        rocksdb_column_family_handle_t* column_family,
+        const char* const* input_file_names,
+        size_t num_input_files,
+        int output_level,
+        char** errptr) {
+        std::vector<std::string> names(input_file_names, input_file_names + num_input_files);
+        SaveError(errptr, db->rep->CompactFiles(opt->rep, column_family->rep, names, output_level));
+    }
+
+    unsigned char rocksdb_key_changed_since(
+        rocksdb_t* db,
+        rocksdb_column_family_handle_t* column_family,
+        const char* key,
+        size_t keylen,
+        uint64_t seq,
+        char** errptr) {
+        ColumnFamilyHandle* cf = column_family != nullptr
+            ? column_family->rep
+            : db->rep->DefaultColumnFamily();
+        Slice user_key(key, keylen);
+        // the newest version of the key comes first
+        std::vector<KeyVersion> versions;
+        if (SaveError(errptr, rocksdb::GetAllKeyVersions(
+                db->rep, cf, user_key, user_key, 1, &versions))) {
+            return 0;
+        }
+        return !versions.empty()
+            && Slice(versions[0].user_key) == user_key
+            && versions[0].sequence > seq;
+    }
+
+    void rocksdb_suggest_compact_range_cf(
+        rocksdb_t* db,
+        rocksdb_column_family_handle_t* column_family,
+        const char* start_key,
+        size_t start_key_len,
+        const char* limit_key,
+        size_t limit_key_len,
+        char** errptr) {
+        Slice a, b;
+        SaveError(errptr, rocksdb::experimental::SuggestCompactRange(
+            db->rep,
+            column_family->rep,
+            (start_key ? (a = Slice(start_key, start_key_len), &a) : nullptr),
+            (limit_key ? (b = Slice(limit_key, limit_key_len), &b) : nullptr)));
+    }
+
+    char* rocksdb_transaction_get_for_update_validate_cf(
+        rocksdb_transaction_t* txn,
+        const rocksdb_readoptions_t* options,
+        rocksdb_column_family_handle_t* column_family,
+        const char* key,
+        size_t klen,
+        size_t* vlen,
+        unsigned char exclusive,
+        unsigned char do_validate,
+        char** errptr) {
+        char* result = nullptr;
+        std::string tmp;
+        Status s = column_family != nullptr
+            ? txn->rep->GetForUpdate(options->rep, column_family->rep,
+                                     Slice(key, klen), &tmp, exclusive, do_validate)
+            : txn->rep->GetForUpdate(options->rep, Slice(key, klen), &tmp,
+                                     exclusive, do_validate);
+        if (s.ok()) {
+            *vlen = tmp.size();
+            result = static_cast<char*>(malloc(tmp.size()));
+            memcpy(result, tmp.data(), tmp.size());
+        } else {
+            *vlen = 0;
+            if (!s.IsNotFound()) {
+                SaveError(errptr, s);
+            }
+        }
+        return result;
+    }
+
+    struct rocksdb_table_filter_state_t {
+        void* state;
+        void (*destructor)(void*);
+        unsigned char (*filter)(void*, const rocksdb_tableproperties_t*);
+
+        ~rocksdb_table_filter_state_t() {
+            (*destructor)(state);
+        }
+    };
+
+    void rocksdb_readoptions_set_table_filter(
+        rocksdb_readoptions_t* opt,
+        void* state,
+        void (*destructor)(void*),
+        unsigned char (*filter)(void*, const rocksdb_tableproperties_t*)) {
+        std::shared_ptr<rocksdb_table_filter_state_t> s(
+            new rocksdb_table_filter_state_t{state, destructor, filter});
+        opt->rep.table_filter = [s](const TableProperties& props) {
+            return (*s->filter)(
+                s->state, reinterpret_cast<const rocksdb_tableproperties_t*>(&props)) != 0;
+        };
+    }
+
+    static const TableProperties& table_properties(const rocksdb_tableproperties_t* props) {
+        return *reinterpret_cast<const TableProperties*>(props);
+    }
+
+    uint64_t rocksdb_tableproperties_num_entries(const rocksdb_tableproperties_t* props) {
+        return table_properties(props).num_entries;
+    }
+
+    uint64_t rocksdb_tableproperties_num_deletions(const rocksdb_tableproperties_t* props) {
+        return table_properties(props).num_deletions;
+    }
+
+    uint64_t rocksdb_tableproperties_num_data_blocks(const rocksdb_tableproperties_t* props) {
+        return table_properties(props).num_data_blocks;
+    }
+
+    uint64_t rocksdb_tableproperties_data_size(const rocksdb_tableproperties_t* props) {
+        return table_properties(props).data_size;
+    }
+
+    uint64_t rocksdb_tableproperties_raw_key_size(const rocksdb_tableproperties_t* props) {
+        return table_properties(props).raw_key_size;
+    }
+
+    uint64_t rocksdb_tableproperties_raw_value_size(const rocksdb_tableproperties_t* props) {
+        return table_properties(props).raw_value_size;
+    }
+
+    const char* rocksdb_tableproperties_column_family_name(
+        const rocksdb_tableproperties_t* props,
+        size_t* name_len) {
+        const std::string& name = table_properties(props).column_family_name;
+        *name_len = name.size();
+        return name.data();
+    }
+
+    const char* rocksdb_tableproperties_user_collected_property(
+        const rocksdb_tableproperties_t* props,
+        const char* name,
+        size_t name_len,
+        size_t* value_len) {
+        const auto& collected = table_properties(props).user_collected_properties;
+        auto it = collected.find(std::string(name, name_len));
+        if (it == collected.end()) {
+            *value_len = 0;
+            return nullptr;
+        }
+        *value_len = it->second.size();
+        return it->second.data();
+    }
+
+    void rocksdb_get_merge_operands_cf(
+        rocksdb_t* db,
+        const rocksdb_readoptions_t* options,
+        rocksdb_column_family_handle_t* column_family,
+        const char* key,
+        size_t keylen,
+        size_t* num_operands,
+        char*** operands,
+        size_t** operand_lens,
+        char** errptr) {
+        ColumnFamilyHandle* cf = column_family != nullptr
+            ? column_family->rep
+            : db->rep->DefaultColumnFamily();
+        *num_operands = 0;
+        *operands = nullptr;
+        *operand_lens = nullptr;
+
+        GetMergeOperandsOptions merge_opts;
+        merge_opts.expected_max_number_of_operands = 16;
+        std::vector<PinnableSlice> values;
+        int count = 0;
+        Status s;
+        for (;;) {
+            values.resize(merge_opts.expected_max_number_of_operands);
+            s = db->rep->GetMergeOperands(options->rep, cf, Slice(key, keylen),
+                                          values.data(), &merge_opts, &count);
+            if (!s.IsIncomplete()) {
+                break;
+            }
+            // more operands than expected, retry with the reported count
+            merge_opts.expected_max_number_of_operands = count;
+        }
+        if (s.IsNotFound()) {
+            return;
+        }
+        if (SaveError(errptr, s) || count <= 0) {
+            return;
+        }
+
+        *num_operands = static_cast<size_t>(count);
+        *operands = static_cast<char**>(malloc(sizeof(char*) * count));
+        *operand_lens = static_cast<size_t*>(malloc(sizeof(size_t) * count));
+        for (int i = 0; i < count; i++) {
+            (*operand_lens)[i] = values[i].size();
+            (*operands)[i] = static_cast<char*>(malloc(values[i].size()));
+            memcpy((*operands)[i], values[i].data(), values[i].size());
+        }
+    }
+
+    void rocksdb_set_db_options(
+        rocksdb_t* db,
+        int count,
+        const char* const keys[],
+        const char* const values[],
+        char** errptr) {
+        std::unordered_map<std::string, std::string> options_map;
+        for (int i = 0; i < count; i++) {
+            options_map[keys[i]] = values[i];
+        }
+        SaveError(errptr, db->rep->SetDBOptions(options_map));
+    }
+
+    char* rocksdb_db_identity(
+        rocksdb_t* db,
+        size_t* identity_len,
+        char** errptr) {
+        std::string identity;
+        if (SaveError(errptr, db->rep->GetDbIdentity(identity))) {
+            return nullptr;
+        }
+        *identity_len = identity.size();
+        char* result = static_cast<char*>(malloc(identity.size()));
+        memcpy(result, identity.data(), identity.size());
+        return result;
+    }
+
+    rocksdb_sstfilemanager_t* rocksdb_sstfilemanager_create(rocksdb_env_t* env) {
+        rocksdb_sstfilemanager_t* manager = new rocksdb_sstfilemanager_t;
+        manager->rep.reset(rocksdb::NewSstFileManager(env->rep));
+        return manager;
+    }
+
+    void rocksdb_sstfilemanager_destroy(rocksdb_sstfilemanager_t* manager) {
+        delete manager;
+    }
+
+    void rocksdb_sstfilemanager_set_delete_rate_bytes_per_second(
+        rocksdb_sstfilemanager_t* manager,
+        int64_t delete_rate) {
+        manager->rep->SetDeleteRateBytesPerSecond(delete_rate);
+    }
+
+    uint64_t rocksdb_sstfilemanager_get_total_size(rocksdb_sstfilemanager_t* manager) {
+        return manager->rep->GetTotalSize();
+    }
+
+    void rocksdb_options_set_sst_file_manager(
+        rocksdb_options_t* opt,
+        rocksdb_sstfilemanager_t* manager) {
+        opt->rep.sst_file_manager = manager->rep;
+    }
+
+    void rocksdb_options_set_preserve_internal_time_seconds(
+        rocksdb_options_t* opt,
+        uint64_t seconds) {
+        opt->rep.preserve_internal_time_seconds = seconds;
+    }
+
+    rocksdb_compactionfilterv2_t* rocksdb_compactionfilterv2_create(
+        void* state,
+        void (*destructor)(void*),
+        unsigned char (*filter)(
+            void*,
+            int level,
+            const char* key,
+            size_t key_length,
+            const char* existing_value,
+            size_t value_length,
+            char** new_value,
+            size_t* new_value_length,
+            unsigned char* value_changed,
+            char** skip_until,
+            size_t* skip_until_length),
+        const char* (*name)(void*)) {
+        rocksdb_compactionfilterv2_t* result = new rocksdb_compactionfilterv2_t;
+        result->state_ = state;
+        result->destructor_ = destructor;
+        result->filter_ = filter;
+        result->name_ = name;
+        return result;
+    }
+
+    void rocksdb_options_set_compaction_filter_v2(
+        rocksdb_options_t* opt,
+        rocksdb_compactionfilterv2_t* filter) {
+        opt->rep.compaction_filter = filter;
+    }
+
+    rocksdb_compactionfilterfactoryv2_t* rocksdb_compactionfilterfactoryv2_create(
+        void* state,
+        void (*destructor)(void*),
+        rocksdb_compactionfilterv2_t* (*create_compaction_filter)(
+            void*,
+            rocksdb_compactionfiltercontext_t* context),
+        const char* (*name)(void*)) {
+        rocksdb_compactionfilterfactoryv2_t* result = new rocksdb_compactionfilterfactoryv2_t;
+        result->state_ = state;
+        result->destructor_ = destructor;
+        result->create_compaction_filter_ = create_compaction_filter;
+        result->name_ = name;
+        return result;
+    }
+
+    void rocksdb_options_set_compaction_filter_factory_v2(
+        rocksdb_options_t* opt,
+        rocksdb_compactionfilterfactoryv2_t* factory) {
+        opt->rep.compaction_filter_factory =
+            std::shared_ptr<CompactionFilterFactory>(factory);
+    }
+
+    uint64_t rocksdb_options_statistics_get_ticker_count_by_name(
+        rocksdb_options_t* opt,
+        const char* name) {
+        Statistics* statistics = opt->rep.statistics.get();
+        if (statistics == nullptr) {
+            return 0;
+        }
+        for (const auto& ticker : rocksdb::TickersNameMap) {
+            if (ticker.second == name) {
+                return statistics->getTickerCount(ticker.first);
+            }
+        }
+        return 0;
+    }
+
+    void rocksdb_ingest_external_file_bytes_cf(
+        rocksdb_t* db,
+        rocksdb_column_family_handle_t* column_family,
+        const char* staging_dir,
+        const char* data,
+        size_t data_len,
+        const rocksdb_ingestexternalfileoptions_t* opt,
+        char** errptr) {
+        Env* env = db->rep->GetEnv();
+        if (SaveError(errptr, env->CreateDirIfMissing(staging_dir))) {
+            return;
+        }
+        std::string fname = std::string(staging_dir) + "/" + env->GenerateUniqueId() + ".ingest.sst";
+        if (SaveError(errptr, rocksdb::WriteStringToFile(env, Slice(data, data_len), fname, true))) {
+            return;
+        }
+        ColumnFamilyHandle* cf = column_family == nullptr
+            ? db->rep->DefaultColumnFamily()
+            : column_family->rep;
+        Status s = db->rep->IngestExternalFile(cf, {fname}, opt->rep);
+        // the staged file is already gone when it was moved into the DB
+        env->DeleteFile(fname).PermitUncheckedError();
+        SaveError(errptr, s);
+    }
+
+    char* rocksdb_env_read_file(
+        rocksdb_env_t* env,
+        const char* fname,
+        size_t* data_len,
+        char** errptr) {
+        std::string data;
+        if (SaveError(errptr, rocksdb::ReadFileToString(env->rep, fname, &data))) {
+            return nullptr;
+        }
+        *data_len = data.size();
+        char* result = static_cast<char*>(malloc(data.size()));
+        memcpy(result, data.data(), data.size());
+        return result;
+    }
+
+    void rocksdb_options_set_allow_data_in_errors(rocksdb_options_t* opt, unsigned char v) {
+        opt->rep.allow_data_in_errors = v;
+    }
+
+    void rocksdb_options_set_paranoid_file_checks(rocksdb_options_t* opt, unsigned char v) {
+        opt->rep.paranoid_file_checks = v;
+    }
+
+    unsigned char rocksdb_snapshot_try_get_sequence_number(
+        const rocksdb_snapshot_t* snapshot,
+        uint64_t* seq) {
+        // transaction snapshots are empty when the transaction doesn't set one
+        if (snapshot == nullptr || snapshot->rep == nullptr) {
+            return 0;
+        }
+        *seq = snapshot->rep->GetSequenceNumber();
+        return 1;
+    }
+
+    rocksdb_eventlistener_t* rocksdb_eventlistener_create(
+        void* state,
+        void (*destructor)(void*),
+        void (*on_flush_completed)(
+            void*,
+            const char* cf_name,
+            size_t cf_name_length,
+            const char* file_path,
+            size_t file_path_length,
+            uint64_t smallest_seqno,
+            uint64_t largest_seqno),
+        void (*on_compaction_completed)(
+            void*,
+            const char* cf_name,
+            size_t cf_name_length,
+            const char* errmsg,
+            int base_input_level,
+            int output_level,
+            size_t num_input_files,
+            size_t num_output_files)) {
+        rocksdb_eventlistener_t* result = new rocksdb_eventlistener_t;
+        result->state_ = state;
+        result->destructor_ = destructor;
+        result->on_flush_completed_ = on_flush_completed;
+        result->on_compaction_completed_ = on_compaction_completed;
+        return result;
+    }
+
+    void rocksdb_options_add_event_listener(
+        rocksdb_options_t* opt,
+        rocksdb_eventlistener_t* listener) {
+        opt->rep.listeners.emplace_back(listener);
+    }
+
+    rocksdb_tablepropertiescollector_t* rocksdb_tablepropertiescollector_create(
+        void* state,
+        void (*destructor)(void*),
+        void (*add)(
+            void*,
+            const char* key,
+            size_t key_length,
+            const char* value,
+            size_t value_length),
+        void (*finish)(void*, rocksdb_usercollectedproperties_t* props),
+        const char* (*name)(void*)) {
+        rocksdb_tablepropertiescollector_t* result = new rocksdb_tablepropertiescollector_t;
+        result->state_ = state;
+        result->destructor_ = destructor;
+        result->add_ = add;
+        result->finish_ = finish;
+        result->name_ = name;
+        return result;
+    }
+
+    void rocksdb_usercollectedproperties_add(
+        rocksdb_usercollectedproperties_t* props,
+        const char* name,
+        size_t name_length,
+        const char* value,
+        size_t value_length) {
+        reinterpret_cast<UserCollectedProperties*>(props)->insert(
+            {std::string(name, name_length), std::string(value, value_length)});
+    }
+
+    rocksdb_tablepropertiescollectorfactory_t* rocksdb_tablepropertiescollectorfactory_create(
+        void* state,
+        void (*destructor)(void*),
+        rocksdb_tablepropertiescollector_t* (*create_collector)(
+            void*, uint32_t column_family_id),
+        const char* (*name)(void*)) {
+        rocksdb_tablepropertiescollectorfactory_t* result =
+            new rocksdb_tablepropertiescollectorfactory_t;
+        result->state_ = state;
+        result->destructor_ = destructor;
+        result->create_collector_ = create_collector;
+        result->name_ = name;
+        return result;
+    }
+
+    void rocksdb_options_add_table_properties_collector_factory(
+        rocksdb_options_t* opt,
+        rocksdb_tablepropertiescollectorfactory_t* factory) {
+        opt->rep.table_properties_collector_factories.emplace_back(factory);
+    }
 }
@@ -12,6 +12,15 @@ typedef struct {
     rocksdb_options_t* db_opts;
     rocksdb_column_family_descriptors_t* cf_descs;
 } rocksdb_fulloptions_t;
+typedef struct rocksdb_compactionoptions_t          rocksdb_compactionoptions_t;
+typedef struct rocksdb_tableproperties_t            rocksdb_tableproperties_t;
+typedef struct rocksdb_sstfilemanager_t             rocksdb_sstfilemanager_t;
+typedef struct rocksdb_compactionfilterv2_t         rocksdb_compactionfilterv2_t;
+typedef struct rocksdb_compactionfilterfactoryv2_t  rocksdb_compactionfilterfactoryv2_t;
+typedef struct rocksdb_eventlistener_t              rocksdb_eventlistener_t;
+typedef struct rocksdb_tablepropertiescollector_t   rocksdb_tablepropertiescollector_t;
+typedef struct rocksdb_tablepropertiescollectorfactory_t rocksdb_tablepropertiescollectorfactory_t;
+typedef struct rocksdb_usercollectedproperties_t    rocksdb_usercollectedproperties_t;
 
 extern ROCKSDB_LIBRARY_API
     rocksdb_cache_t* rocksdb_null_cache();
@@ -19,6 +28,9 @@ extern ROCKSDB_LIBRARY_API
 extern ROCKSDB_LIBRARY_API
     rocksdb_options_t* rocksdb_options_clone(rocksdb_options_t* options);
 
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_set_two_write_queues(rocksdb_options_t* opt, unsigned char v);
+
 extern ROCKSDB_LIBRARY_API
     rocksdb_column_family_descriptors_t* rocksdb_column_family_descriptors_create();
 
@@ -42,6 +54,314 @@ extern ROCKSDB_LIBRARY_API
         rocksdb_cache_t* cache,
         char** errptr);
 
+extern ROCKSDB_LIBRARY_API
+    rocksdb_compactionoptions_t* rocksdb_compactionoptions_create();
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_compactionoptions_destroy(rocksdb_compactionoptions_t* opt);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_compactionoptions_set_compression(rocksdb_compactionoptions_t* opt, int compression);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_compactionoptions_set_output_file_size_limit(rocksdb_compactionoptions_t* opt, uint64_t limit);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_compactionoptions_set_max_subcompactions(rocksdb_compactionoptions_t* opt, uint32_t max);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_compact_files(
+        rocksdb_t* db,
+        const rocksdb_compactionoptions_t* opt,
+        const char* const* input_file_names,
+        size_t num_input_files,
+        int output_level,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_compact_files_cf(
+        rocksdb_t* db,
+        const rocksdb_compactionoptions_t* opt,
+        rocksdb_column_family_handle_t* column_family,
+        const char* const* input_file_names,
+        size_t num_input_files,
+        int output_level,
+        char** errptr);
+
+/* Returns 1 if `key` was written after sequence number `seq`, i.e. a
+   transaction that started tracking it at `seq` would conflict on commit.
+   Range deletions covering `key` are not taken into account. */
+extern ROCKSDB_LIBRARY_API
+    unsigned char rocksdb_key_changed_since(
+        rocksdb_t* db,
+        rocksdb_column_family_handle_t* column_family,
+        const char* key,
+        size_t keylen,
+        uint64_t seq,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_suggest_compact_range_cf(
+        rocksdb_t* db,
+        rocksdb_column_family_handle_t* column_family,
+        const char* start_key,
+        size_t start_key_len,
+        const char* limit_key,
+        size_t limit_key_len,
+        char** errptr);
+
+/* Same as rocksdb_transaction_get_for_update_cf, but exposes `do_validate`.
+   A null column family means the default column family. */
+extern ROCKSDB_LIBRARY_API
+    char* rocksdb_transaction_get_for_update_validate_cf(
+        rocksdb_transaction_t* txn,
+        const rocksdb_readoptions_t* options,
+        rocksdb_column_family_handle_t* column_family,
+        const char* key,
+        size_t klen,
+        size_t* vlen,
+        unsigned char exclusive,
+        unsigned char do_validate,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_readoptions_set_table_filter(
+        rocksdb_readoptions_t* opt,
+        void* state,
+        void (*destructor)(void*),
+        unsigned char (*filter)(void*, const rocksdb_tableproperties_t*));
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_tableproperties_num_entries(const rocksdb_tableproperties_t* props);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_tableproperties_num_deletions(const rocksdb_tableproperties_t* props);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_tableproperties_num_data_blocks(const rocksdb_tableproperties_t* props);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_tableproperties_data_size(const rocksdb_tableproperties_t* props);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_tableproperties_raw_key_size(const rocksdb_tableproperties_t* props);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_tableproperties_raw_value_size(const rocksdb_tableproperties_t* props);
+
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_tableproperties_column_family_name(
+        const rocksdb_tableproperties_t* props,
+        size_t* name_len);
+
+/* Returns null if the property does not exist. */
+extern ROCKSDB_LIBRARY_API
+    const char* rocksdb_tableproperties_user_collected_property(
+        const rocksdb_tableproperties_t* props,
+        const char* name,
+        size_t name_len,
+        size_t* value_len);
+
+/* Returns the unmerged operands of a key, oldest first. The arrays and each
+   operand are malloc'd and owned by the caller. */
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_get_merge_operands_cf(
+        rocksdb_t* db,
+        const rocksdb_readoptions_t* options,
+        rocksdb_column_family_handle_t* column_family,
+        const char* key,
+        size_t keylen,
+        size_t* num_operands,
+        char*** operands,
+        size_t** operand_lens,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_set_db_options(
+        rocksdb_t* db,
+        int count,
+        const char* const keys[],
+        const char* const values[],
+        char** errptr);
+
+/* Returns the DB identity as a malloc'd string owned by the caller. */
+extern ROCKSDB_LIBRARY_API
+    char* rocksdb_db_identity(
+        rocksdb_t* db,
+        size_t* identity_len,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_sstfilemanager_t* rocksdb_sstfilemanager_create(rocksdb_env_t* env);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_sstfilemanager_destroy(rocksdb_sstfilemanager_t* manager);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_sstfilemanager_set_delete_rate_bytes_per_second(
+        rocksdb_sstfilemanager_t* manager,
+        int64_t delete_rate);
+
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_sstfilemanager_get_total_size(rocksdb_sstfilemanager_t* manager);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_set_sst_file_manager(
+        rocksdb_options_t* opt,
+        rocksdb_sstfilemanager_t* manager);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_set_preserve_internal_time_seconds(
+        rocksdb_options_t* opt,
+        uint64_t seconds);
+
+/* Like rocksdb_compactionfilter_create, but the filter can also return 2
+   (remove and skip until) together with the key to skip until in
+   skip_until / skip_until_length. */
+extern ROCKSDB_LIBRARY_API
+    rocksdb_compactionfilterv2_t* rocksdb_compactionfilterv2_create(
+        void* state,
+        void (*destructor)(void*),
+        unsigned char (*filter)(
+            void*,
+            int level,
+            const char* key,
+            size_t key_length,
+            const char* existing_value,
+            size_t value_length,
+            char** new_value,
+            size_t* new_value_length,
+            unsigned char* value_changed,
+            char** skip_until,
+            size_t* skip_until_length),
+        const char* (*name)(void*));
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_set_compaction_filter_v2(
+        rocksdb_options_t* opt,
+        rocksdb_compactionfilterv2_t* filter);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_compactionfilterfactoryv2_t* rocksdb_compactionfilterfactoryv2_create(
+        void* state,
+        void (*destructor)(void*),
+        rocksdb_compactionfilterv2_t* (*create_compaction_filter)(
+            void*,
+            rocksdb_compactionfiltercontext_t* context),
+        const char* (*name)(void*));
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_set_compaction_filter_factory_v2(
+        rocksdb_options_t* opt,
+        rocksdb_compactionfilterfactoryv2_t* factory);
+
+/* Looks a ticker up by its name, e.g. "rocksdb.block.cache.hit", since the
+   numeric ticker types shift between releases. Returns 0 when statistics
+   are disabled or the name is unknown. */
+extern ROCKSDB_LIBRARY_API
+    uint64_t rocksdb_options_statistics_get_ticker_count_by_name(
+        rocksdb_options_t* opt,
+        const char* name);
+
+/* Writes an in-memory SST file through the env of the DB into staging_dir,
+   and ingests it. column_family may be NULL for the default one. */
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_ingest_external_file_bytes_cf(
+        rocksdb_t* db,
+        rocksdb_column_family_handle_t* column_family,
+        const char* staging_dir,
+        const char* data,
+        size_t data_len,
+        const rocksdb_ingestexternalfileoptions_t* opt,
+        char** errptr);
+
+/* Returns the content of a file read through env, as a malloc'd buffer owned
+   by the caller. */
+extern ROCKSDB_LIBRARY_API
+    char* rocksdb_env_read_file(
+        rocksdb_env_t* env,
+        const char* fname,
+        size_t* data_len,
+        char** errptr);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_set_allow_data_in_errors(rocksdb_options_t* opt, unsigned char v);
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_set_paranoid_file_checks(rocksdb_options_t* opt, unsigned char v);
+
+extern ROCKSDB_LIBRARY_API
+    unsigned char rocksdb_snapshot_try_get_sequence_number(
+        const rocksdb_snapshot_t* snapshot,
+        uint64_t* seq);
+
+/* The listener is owned by the options it is added to, and by the DBs opened
+   with them; `destructor` is called once the last of them is gone. `errmsg`
+   is NULL when the compaction succeeded. */
+extern ROCKSDB_LIBRARY_API
+    rocksdb_eventlistener_t* rocksdb_eventlistener_create(
+        void* state,
+        void (*destructor)(void*),
+        void (*on_flush_completed)(
+            void*,
+            const char* cf_name,
+            size_t cf_name_length,
+            const char* file_path,
+            size_t file_path_length,
+            uint64_t smallest_seqno,
+            uint64_t largest_seqno),
+        void (*on_compaction_completed)(
+            void*,
+            const char* cf_name,
+            size_t cf_name_length,
+            const char* errmsg,
+            int base_input_level,
+            int output_level,
+            size_t num_input_files,
+            size_t num_output_files));
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_add_event_listener(
+        rocksdb_options_t* opt,
+        rocksdb_eventlistener_t* listener);
+
+/* `add` is called with every key put into the table being built, `finish`
+   once the table is complete, to store its properties through
+   rocksdb_usercollectedproperties_add. */
+extern ROCKSDB_LIBRARY_API
+    rocksdb_tablepropertiescollector_t* rocksdb_tablepropertiescollector_create(
+        void* state,
+        void (*destructor)(void*),
+        void (*add)(
+            void*,
+            const char* key,
+            size_t key_length,
+            const char* value,
+            size_t value_length),
+        void (*finish)(void*, rocksdb_usercollectedproperties_t* props),
+        const char* (*name)(void*));
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_usercollectedproperties_add(
+        rocksdb_usercollectedproperties_t* props,
+        const char* name,
+        size_t name_length,
+        const char* value,
+        size_t value_length);
+
+extern ROCKSDB_LIBRARY_API
+    rocksdb_tablepropertiescollectorfactory_t* rocksdb_tablepropertiescollectorfactory_create(
+        void* state,
+        void (*destructor)(void*),
+        rocksdb_tablepropertiescollector_t* (*create_collector)(
+            void*, uint32_t column_family_id),
+        const char* (*name)(void*));
+
+extern ROCKSDB_LIBRARY_API
+    void rocksdb_options_add_table_properties_collector_factory(
+        rocksdb_options_t* opt,
+        rocksdb_tablepropertiescollectorfactory_t* factory);
+
 #ifdef __cplusplus
 }  /* end extern "C" */
 #endif
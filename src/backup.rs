@@ -407,3 +407,43 @@ fn restore_from_backup() {
         }
     }
 }
+
+#[test]
+fn restore_after_destroy() {
+    use crate::ops::{Get, Open, Put};
+    use crate::{Options, TemporaryDBPath};
+
+    let path = TemporaryDBPath::new();
+    let backup_path = TemporaryDBPath::new();
+    let backup_opts = BackupEngineOptions::default();
+    let mut backup_engine = BackupEngine::open(&backup_opts, &backup_path).unwrap();
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        backup_engine.create_new_backup_flush(&db, true).unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        backup_engine.create_new_backup_flush(&db, true).unwrap();
+    }
+
+    let info = backup_engine.get_backup_info();
+    assert_eq!(info.len(), 2);
+    assert!(info[0].backup_id < info[1].backup_id);
+    assert!(info.iter().all(|i| i.timestamp > 0 && i.num_files > 0));
+
+    DB::destroy(&Options::default(), &path).unwrap();
+    assert!(
+        DB::open_default(&path)
+            .unwrap()
+            .get(b"k1")
+            .unwrap()
+            .is_none()
+    );
+    DB::destroy(&Options::default(), &path).unwrap();
+
+    backup_engine
+        .restore_from_latest_backup(&path, &path, &RestoreOptions::default())
+        .unwrap();
+    let db = DB::open_default(&path).unwrap();
+    assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+    assert_eq!(&*db.get(b"k2").unwrap().unwrap(), b"v2");
+}
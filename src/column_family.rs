@@ -44,6 +44,12 @@ impl ColumnFamily {
     pub(crate) fn new(handle: *mut ffi::rocksdb_column_family_handle_t) -> ColumnFamily {
         ColumnFamily { inner: handle }
     }
+
+    /// Returns the id of the column family, as passed to the
+    /// `WriteBatchIterator` callbacks. The default column family has id 0.
+    pub fn id(&self) -> u32 {
+        unsafe { ffi::rocksdb_column_family_handle_get_id(self.inner) }
+    }
 }
 
 impl Handle<ffi::rocksdb_column_family_handle_t> for ColumnFamily {
@@ -14,6 +14,7 @@
 //
 
 use libc::{c_char, c_int, c_uchar, c_void, size_t};
+use std::cell::RefCell;
 use std::ffi::{CStr, CString};
 use std::slice;
 
@@ -29,6 +30,22 @@ pub enum Decision {
     Remove,
     /// Change the value for the key
     Change(&'static [u8]),
+    /// Change the value for the key to a value computed by the filter
+    ChangeValue(Vec<u8>),
+    /// Remove the object and all the following keys up to, but not
+    /// including, the given key, without passing them to the filter. This
+    /// is much faster than removing a contiguous range key by key.
+    ///
+    /// The skipped keys are dropped even if they are visible in a snapshot,
+    /// and a key not greater than the current one is treated as `Keep`.
+    RemoveAndSkipUntil(Vec<u8>),
+}
+
+thread_local! {
+    // RocksDB copies a changed value as soon as the filter callback returns,
+    // so an owned value only has to outlive the callback on this thread.
+    static CHANGED_VALUE: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
+    static SKIP_UNTIL: RefCell<Vec<u8>> = const { RefCell::new(Vec::new()) };
 }
 
 /// CompactionFilter allows an application to modify/delete a key-value at
@@ -125,12 +142,14 @@ pub unsafe extern "C" fn filter_callback<F>(
     new_value: *mut *mut c_char,
     new_value_length: *mut size_t,
     value_changed: *mut c_uchar,
+    skip_until: *mut *mut c_char,
+    skip_until_length: *mut size_t,
 ) -> c_uchar
 where
     F: CompactionFilter,
 {
     unsafe {
-        use self::Decision::{Change, Keep, Remove};
+        use self::Decision::{Change, ChangeValue, Keep, Remove, RemoveAndSkipUntil};
 
         let cb = &mut *(raw_cb as *mut F);
         let key = slice::from_raw_parts(raw_key as *const u8, key_length);
@@ -145,6 +164,21 @@ where
                 *value_changed = 1_u8;
                 0
             }
+            ChangeValue(newval) => CHANGED_VALUE.with(|changed| {
+                let mut changed = changed.borrow_mut();
+                *changed = newval;
+                *new_value = changed.as_ptr() as *mut c_char;
+                *new_value_length = changed.len() as size_t;
+                *value_changed = 1_u8;
+                0
+            }),
+            RemoveAndSkipUntil(until) => SKIP_UNTIL.with(|skip| {
+                let mut skip = skip.borrow_mut();
+                *skip = until;
+                *skip_until = skip.as_ptr() as *mut c_char;
+                *skip_until_length = skip.len() as size_t;
+                2
+            }),
         }
     }
 }
@@ -73,7 +73,7 @@ impl CompactionFilterContext {
 pub unsafe extern "C" fn create_compaction_filter_callback<F>(
     raw_self: *mut c_void,
     context: *mut ffi::rocksdb_compactionfiltercontext_t,
-) -> *mut ffi::rocksdb_compactionfilter_t
+) -> *mut ffi::rocksdb_compactionfilterv2_t
 where
     F: CompactionFilterFactory,
 {
@@ -84,7 +84,7 @@ where
 
         let filter_ptr = Box::into_raw(filter);
 
-        ffi::rocksdb_compactionfilter_create(
+        ffi::rocksdb_compactionfilterv2_create(
             filter_ptr as *mut c_void,
             Some(compaction_filter::destructor_callback::<F::Filter>),
             Some(compaction_filter::filter_callback::<F::Filter>),
@@ -13,7 +13,7 @@
 // limitations under the License.
 //
 
-use libc::{c_char, c_int, c_void, size_t};
+use libc::{c_char, c_int, c_uchar, c_void, size_t};
 use std::cmp::Ordering;
 use std::ffi::CString;
 use std::slice;
@@ -57,3 +57,90 @@ pub unsafe extern "C" fn compare_callback(
         }
     }
 }
+
+/// Compares two timestamps.
+pub type CompareTsFn = fn(&[u8], &[u8]) -> Ordering;
+
+/// Compares two keys ignoring their timestamps. The flags tell whether each
+/// key carries a timestamp suffix.
+pub type CompareWithoutTsFn = fn(&[u8], bool, &[u8], bool) -> Ordering;
+
+pub struct ComparatorWithTsCallback {
+    pub name: CString,
+    pub compare_fn: CompareFn,
+    pub compare_ts_fn: CompareTsFn,
+    pub compare_without_ts_fn: CompareWithoutTsFn,
+}
+
+fn ordering_to_int(ordering: Ordering) -> c_int {
+    match ordering {
+        Ordering::Less => -1,
+        Ordering::Equal => 0,
+        Ordering::Greater => 1,
+    }
+}
+
+pub unsafe extern "C" fn destructor_with_ts_callback(raw_cb: *mut c_void) {
+    unsafe {
+        let _ = Box::from_raw(raw_cb as *mut ComparatorWithTsCallback);
+    }
+}
+
+pub unsafe extern "C" fn name_with_ts_callback(raw_cb: *mut c_void) -> *const c_char {
+    unsafe {
+        let cb = &*(raw_cb as *mut ComparatorWithTsCallback);
+        cb.name.as_ptr()
+    }
+}
+
+pub unsafe extern "C" fn compare_with_ts_callback(
+    raw_cb: *mut c_void,
+    a_raw: *const c_char,
+    a_len: size_t,
+    b_raw: *const c_char,
+    b_len: size_t,
+) -> c_int {
+    unsafe {
+        let cb = &*(raw_cb as *mut ComparatorWithTsCallback);
+        let a: &[u8] = slice::from_raw_parts(a_raw as *const u8, a_len);
+        let b: &[u8] = slice::from_raw_parts(b_raw as *const u8, b_len);
+        ordering_to_int((cb.compare_fn)(a, b))
+    }
+}
+
+pub unsafe extern "C" fn compare_ts_callback(
+    raw_cb: *mut c_void,
+    a_ts: *const c_char,
+    a_ts_len: size_t,
+    b_ts: *const c_char,
+    b_ts_len: size_t,
+) -> c_int {
+    unsafe {
+        let cb = &*(raw_cb as *mut ComparatorWithTsCallback);
+        let a: &[u8] = slice::from_raw_parts(a_ts as *const u8, a_ts_len);
+        let b: &[u8] = slice::from_raw_parts(b_ts as *const u8, b_ts_len);
+        ordering_to_int((cb.compare_ts_fn)(a, b))
+    }
+}
+
+pub unsafe extern "C" fn compare_without_ts_callback(
+    raw_cb: *mut c_void,
+    a_raw: *const c_char,
+    a_len: size_t,
+    a_has_ts: c_uchar,
+    b_raw: *const c_char,
+    b_len: size_t,
+    b_has_ts: c_uchar,
+) -> c_int {
+    unsafe {
+        let cb = &*(raw_cb as *mut ComparatorWithTsCallback);
+        let a: &[u8] = slice::from_raw_parts(a_raw as *const u8, a_len);
+        let b: &[u8] = slice::from_raw_parts(b_raw as *const u8, b_len);
+        ordering_to_int((cb.compare_without_ts_fn)(
+            a,
+            a_has_ts != 0,
+            b,
+            b_has_ts != 0,
+        ))
+    }
+}
@@ -17,7 +17,7 @@ use crate::ffi;
 use crate::ffi_util::to_cpath;
 
 use crate::{
-    ColumnFamily, DBRawIterator, Error, Options, ReadOptions, Snapshot,
+    ColumnFamily, DBRawIterator, Error, Options, ReadOptions, Snapshot, WalIterator,
     db_options::OptionsMustOutliveDB,
     handle::Handle,
     open_raw::{OpenRaw, OpenRawFFI},
@@ -105,6 +105,10 @@ unsafe impl Send for DB {}
 unsafe impl Sync for DB {}
 
 impl DB {
+    /// List the column families of the database at `path`, including `default`.
+    ///
+    /// All existing column families must be passed when opening a database, so the result
+    /// can be handed straight to `open_cf`.
     pub fn list_cf<P: AsRef<Path>>(opts: &Options, path: P) -> Result<Vec<String>, Error> {
         let cpath = to_cpath(
             path,
@@ -154,6 +158,32 @@ impl DB {
         self.path.as_path()
     }
 
+    /// Returns the unique identity of the database, as stored in the
+    /// `IDENTITY` file (or in the MANIFEST with `write_dbid_to_manifest`).
+    /// The identity stays the same across reopens.
+    pub fn get_db_identity(&self) -> Result<String, Error> {
+        let mut length = 0;
+
+        unsafe {
+            let ptr = ffi_try!(ffi::rocksdb_db_identity(self.inner, &mut length));
+            let bytes = slice::from_raw_parts(ptr as *const u8, length);
+            let identity = String::from_utf8_lossy(bytes).into_owned();
+            ffi::rocksdb_free(ptr as *mut libc::c_void);
+            Ok(identity)
+        }
+    }
+
+    /// Returns the sequence number of the most recent write.
+    pub fn latest_sequence_number(&self) -> u64 {
+        unsafe { ffi::rocksdb_get_latest_sequence_number(self.inner) }
+    }
+
+    /// Returns an iterator over the write batches in the WAL, starting with
+    /// the batch containing `seq_number`.
+    pub fn get_updates_since(&self, seq_number: u64) -> Result<WalIterator<'_>, Error> {
+        WalIterator::new(self, seq_number)
+    }
+
     pub fn snapshot(&self) -> Snapshot<'_> {
         let snapshot = unsafe { ffi::rocksdb_create_snapshot(self.inner) };
         Snapshot {
@@ -161,6 +191,18 @@ impl DB {
             inner: snapshot,
         }
     }
+
+    /// Takes a snapshot and returns it together with its sequence number, for
+    /// change data capture: read the consistent state from the snapshot, then
+    /// tail the writes made after it with `get_updates_since(seq + 1)`.
+    ///
+    /// The sequence number is the one of the snapshot itself, so no write can
+    /// slip in between the two.
+    pub fn snapshot_and_updates_since(&self) -> (Snapshot<'_>, u64) {
+        let snapshot = self.snapshot();
+        let seq = snapshot.sequence_number();
+        (snapshot, seq)
+    }
 }
 
 impl Drop for DB {
@@ -181,11 +223,12 @@ impl fmt::Debug for DB {
 }
 
 impl Iterate for DB {
-    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &'b ReadOptions) -> DBRawIterator<'b> {
         unsafe {
             DBRawIterator {
                 inner: ffi::rocksdb_create_iterator(self.inner, readopts.handle()),
                 db: PhantomData,
+                readopts: None,
             }
         }
     }
@@ -195,7 +238,7 @@ impl IterateCF for DB {
     fn get_raw_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBRawIterator<'b>, Error> {
         unsafe {
             Ok(DBRawIterator {
@@ -205,6 +248,7 @@ impl IterateCF for DB {
                     cf_handle.inner,
                 ),
                 db: PhantomData,
+                readopts: None,
             })
         }
     }
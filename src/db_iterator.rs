@@ -14,9 +14,12 @@
 //
 
 use crate::ops::Iterate;
+use crate::{Error, ReadOptions};
 use libc::{c_char, c_uchar, size_t};
 use std::marker::PhantomData;
+use std::mem;
 use std::slice;
+use std::sync::Arc;
 
 /// An iterator over a database or column family, with specifiable
 /// ranges and direction.
@@ -75,6 +78,9 @@ unsafe impl Sync for DBRawIterator<'_> {}
 pub struct DBRawIterator<'a> {
     pub(crate) inner: *mut ffi::rocksdb_iterator_t,
     pub(crate) db: PhantomData<&'a dyn Iterate>,
+    // options created along with the iterator, which own the buffers the
+    // iterator points to, dropped after it
+    pub(crate) readopts: Option<ReadOptions>,
 }
 
 /// An iterator over a database or column family, with specifiable
@@ -140,6 +146,32 @@ impl DBRawIterator<'_> {
         unsafe { ffi::rocksdb_iter_valid(self.inner) != 0 }
     }
 
+    /// Returns the error which made the iterator invalid, if any.
+    ///
+    /// An iterator also becomes invalid when it moves past the end of the
+    /// data, so whenever `valid()` returns false this has to be checked to
+    /// tell an I/O error or a corruption from the end of the iteration.
+    pub fn status(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_iter_get_error(self.inner));
+        }
+        Ok(())
+    }
+
+    /// Updates the iterator to read the latest state of the database, so it
+    /// stops pinning the data it was created on and sees the writes made
+    /// since then.
+    ///
+    /// The iterator is left unpositioned, it has to be seeked again before
+    /// being used. Iterators reading from an explicit snapshot do not
+    /// support this.
+    pub fn refresh(&mut self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_iter_refresh(self.inner));
+        }
+        Ok(())
+    }
+
     /// Seeks to the first key in the database.
     ///
     /// # Examples
@@ -363,7 +395,17 @@ impl Drop for DBRawIterator<'_> {
     }
 }
 
-impl DBIterator<'_> {
+impl<'a> DBIterator<'a> {
+    pub(crate) fn new(raw: DBRawIterator<'a>, mode: IteratorMode<'_>) -> DBIterator<'a> {
+        let mut rv = DBIterator {
+            raw,
+            direction: Direction::Forward, // blown away by set_mode()
+            just_seeked: false,
+        };
+        rv.set_mode(mode);
+        rv
+    }
+
     pub fn set_mode(&mut self, mode: IteratorMode) {
         match mode {
             IteratorMode::Start => {
@@ -422,6 +464,76 @@ impl Iterator for DBIterator<'_> {
     }
 }
 
+/// A `DBIterator` which owns a shared handle to its database instead of
+/// borrowing it, so it can be sent to another thread, e.g. to hand a scan
+/// to a worker.
+///
+/// ```
+/// use ckb_rocksdb::{prelude::*, IteratorMode, OwnedDBIterator};
+/// # use ckb_rocksdb::TemporaryDBPath;
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// let path = "_path_for_rocksdb_storage_owned_iterator";
+/// # let path = TemporaryDBPath::new();
+/// # {
+/// let db = Arc::new(DB::open_default(&path).unwrap());
+/// db.put(b"k1", b"v1").unwrap();
+///
+/// let iter = OwnedDBIterator::new(db.clone(), IteratorMode::Start);
+/// let count = thread::spawn(move || iter.count()).join().unwrap();
+/// assert_eq!(count, 1);
+/// # }
+/// ```
+pub struct OwnedDBIterator<D: Iterate> {
+    // declared first so it is dropped before the options and the database
+    iter: DBIterator<'static>,
+    _readopts: ReadOptions,
+    _db: Arc<D>,
+}
+
+unsafe impl<D: Iterate + Send + Sync> Send for OwnedDBIterator<D> {}
+
+impl<D: Iterate> OwnedDBIterator<D> {
+    pub fn new(db: Arc<D>, mode: IteratorMode<'_>) -> OwnedDBIterator<D> {
+        Self::new_opt(db, mode, ReadOptions::default())
+    }
+
+    /// Opens the iterator with the provided ReadOptions, which are kept
+    /// alive with the iterator.
+    pub fn new_opt(
+        db: Arc<D>,
+        mode: IteratorMode<'_>,
+        readopts: ReadOptions,
+    ) -> OwnedDBIterator<D> {
+        let iter = db.get_iter(&readopts, mode);
+        // the database and the options are owned by the returned value and
+        // outlive the iterator, see the field order
+        let iter = unsafe { mem::transmute::<DBIterator<'_>, DBIterator<'static>>(iter) };
+        OwnedDBIterator {
+            iter,
+            _readopts: readopts,
+            _db: db,
+        }
+    }
+
+    pub fn set_mode(&mut self, mode: IteratorMode) {
+        self.iter.set_mode(mode);
+    }
+
+    pub fn valid(&self) -> bool {
+        self.iter.valid()
+    }
+}
+
+impl<D: Iterate> Iterator for OwnedDBIterator<D> {
+    type Item = KVBytes;
+
+    fn next(&mut self) -> Option<KVBytes> {
+        self.iter.next()
+    }
+}
+
 impl<'a> From<DBIterator<'a>> for DBRawIterator<'a> {
     fn from(iter: DBIterator<'a>) -> DBRawIterator<'a> {
         iter.raw
@@ -19,18 +19,26 @@ use crate::{
 
 use std::ffi::{CStr, CString};
 use std::path::Path;
+use std::slice;
 use std::sync::Arc;
 
 use libc::{self, c_char, c_double, c_int, c_uchar, c_uint, c_void, size_t};
 
 use crate::compaction_filter::{self, CompactionFilterCallback, CompactionFilterFn};
 use crate::compaction_filter_factory::{self, CompactionFilterFactory};
-use crate::comparator::{self, ComparatorCallback, CompareFn};
+use crate::comparator::{
+    self, ComparatorCallback, ComparatorWithTsCallback, CompareFn, CompareTsFn, CompareWithoutTsFn,
+};
+use crate::event_listener::{self, EventListener};
 use crate::ffi;
+use crate::ffi_util::to_cpath;
 use crate::merge_operator::{
     self, MergeFn, MergeOperatorCallback, full_merge_callback, partial_merge_callback,
 };
 use crate::slice_transform::SliceTransform;
+use crate::table_properties::{
+    self, TableFilterFn, TableProperties, TablePropertiesCollectorFactory,
+};
 use std::ptr::NonNull;
 
 pub(crate) struct CacheWrapper {
@@ -55,6 +63,19 @@ impl Cache {
         Cache(Arc::new(CacheWrapper { inner }))
     }
 
+    /// Creates an LRU cache with capacity in bytes which strictly enforces
+    /// its capacity: inserting an entry that does not fit fails instead of
+    /// growing the cache beyond `capacity`.
+    ///
+    /// Reads keep working when an insert fails, the block is just not cached.
+    pub fn new_lru_cache_with_strict_capacity_limit(capacity: size_t) -> Cache {
+        let inner = NonNull::new(unsafe {
+            ffi::rocksdb_cache_create_lru_with_strict_capacity_limit(capacity)
+        })
+        .unwrap();
+        Cache(Arc::new(CacheWrapper { inner }))
+    }
+
     /// Creates a HyperClockCache with capacity in bytes.
     ///
     /// `estimated_entry_charge` is an important tuning parameter. The optimal
@@ -100,6 +121,15 @@ impl Cache {
             ffi::rocksdb_cache_set_capacity(self.0.inner.as_ptr(), capacity);
         }
     }
+
+    /// Makes the cache leak its entries instead of releasing them when it
+    /// is destroyed, which avoids a slow destructor on huge caches at
+    /// shutdown. The memory is reclaimed by the OS when the process exits.
+    pub fn disown_data(&self) {
+        unsafe {
+            ffi::rocksdb_cache_disown_data(self.0.inner.as_ptr());
+        }
+    }
 }
 
 /// An Env is an interface used by the rocksdb implementation to access
@@ -149,6 +179,31 @@ impl Env {
         }
     }
 
+    /// Reads a whole file through this environment, e.g. an SST file built
+    /// with `SstFileWriter` in a mem env, to pass to `ingest_sst_bytes`.
+    pub fn read_file<P: AsRef<Path>>(&self, path: P) -> Result<Vec<u8>, Error> {
+        let cpath = to_cpath(
+            path,
+            "Failed to convert path to CString when reading a file.",
+        )?;
+        let mut length = 0;
+
+        unsafe {
+            let ptr = ffi_try!(ffi::rocksdb_env_read_file(
+                self.0.inner,
+                cpath.as_ptr(),
+                &mut length
+            ));
+            let data = if length == 0 {
+                Vec::new()
+            } else {
+                slice::from_raw_parts(ptr as *const u8, length).to_vec()
+            };
+            ffi::rocksdb_free(ptr as *mut c_void);
+            Ok(data)
+        }
+    }
+
     /// Sets the number of background worker threads of a specific thread pool for this environment.
     /// `LOW` is the default pool.
     ///
@@ -223,11 +278,61 @@ impl Env {
     }
 }
 
+/// Tracks the total size of the SST files of the DBs it is attached to and
+/// rate limits their deletion, to avoid bursts of disk IO when large
+/// compactions drop many files at once.
+///
+/// A manager can be shared by several DBs through `Options::set_sst_file_manager`.
+#[derive(Clone)]
+pub struct SstFileManager(Arc<SstFileManagerWrapper>);
+
+pub(crate) struct SstFileManagerWrapper {
+    inner: *mut ffi::rocksdb_sstfilemanager_t,
+    _env: Env,
+}
+
+impl Drop for SstFileManagerWrapper {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_sstfilemanager_destroy(self.inner);
+        }
+    }
+}
+
+impl SstFileManager {
+    /// Creates a manager that deletes files through `env`.
+    pub fn new(env: &Env) -> Self {
+        let inner = unsafe { ffi::rocksdb_sstfilemanager_create(env.0.inner) };
+        Self(Arc::new(SstFileManagerWrapper {
+            inner,
+            _env: env.clone(),
+        }))
+    }
+
+    /// Limits the rate of SST file deletions, in bytes per second. Files
+    /// waiting for deletion are kept in the trash until their turn comes.
+    ///
+    /// Default: 0, which deletes files immediately
+    pub fn set_delete_rate_bytes_per_second(&self, delete_rate: i64) {
+        unsafe {
+            ffi::rocksdb_sstfilemanager_set_delete_rate_bytes_per_second(self.0.inner, delete_rate);
+        }
+    }
+
+    /// Returns the total size in bytes of the SST files tracked by this
+    /// manager.
+    pub fn get_total_size(&self) -> u64 {
+        unsafe { ffi::rocksdb_sstfilemanager_get_total_size(self.0.inner) }
+    }
+}
+
 #[derive(Default)]
 pub struct OptionsMustOutliveDB {
     pub(crate) env: Option<Env>,
     pub(crate) row_cache: Option<Cache>,
+    pub(crate) sst_file_manager: Option<SstFileManager>,
     pub(crate) block_based: Option<BlockBasedOptionsMustOutliveDB>,
+    pub(crate) info_log: Option<Arc<InfoLogCallback>>,
 }
 
 impl OptionsMustOutliveDB {
@@ -235,10 +340,12 @@ impl OptionsMustOutliveDB {
         Self {
             env: self.env.as_ref().map(Env::clone),
             row_cache: self.row_cache.clone(),
+            sst_file_manager: self.sst_file_manager.clone(),
             block_based: self
                 .block_based
                 .as_ref()
                 .map(BlockBasedOptionsMustOutliveDB::clone),
+            info_log: self.info_log.clone(),
         }
     }
 }
@@ -322,6 +429,8 @@ pub struct Options {
 pub struct WriteOptions {
     option_set_sync: Option<bool>,
     option_disable_wal: Option<bool>,
+    option_set_no_slowdown: Option<bool>,
+    option_set_low_pri: Option<bool>,
     inner: *mut ffi::rocksdb_writeoptions_t,
 }
 
@@ -364,6 +473,10 @@ pub struct ReadOptions {
     option_set_prefix_same_as_start: Option<bool>,
     option_set_total_order_seek: Option<bool>,
     option_set_readahead_size: Option<usize>,
+    option_set_tailing: Option<bool>,
+    option_set_table_filter: Option<TableFilterFn>,
+    option_set_timestamp: Option<Vec<u8>>,
+    option_set_snapshot: Option<*const ffi::rocksdb_snapshot_t>,
     inner: *mut ffi::rocksdb_readoptions_t,
 }
 
@@ -411,6 +524,7 @@ unsafe impl Send for ReadOptions {}
 unsafe impl Send for IngestExternalFileOptions {}
 unsafe impl Send for CacheWrapper {}
 unsafe impl Send for EnvWrapper {}
+unsafe impl Send for SstFileManagerWrapper {}
 
 // Sync is similarly safe for many types because they do not expose interior mutability, and their
 // use within the rocksdb library is generally behind a const reference
@@ -422,6 +536,7 @@ unsafe impl Sync for ReadOptions {}
 unsafe impl Sync for IngestExternalFileOptions {}
 unsafe impl Sync for CacheWrapper {}
 unsafe impl Sync for EnvWrapper {}
+unsafe impl Sync for SstFileManagerWrapper {}
 
 impl Drop for Options {
     fn drop(&mut self) {
@@ -547,6 +662,11 @@ impl BlockBasedOptions {
     /// Sets a [Bloom filter](https://github.com/facebook/rocksdb/wiki/RocksDB-Bloom-Filter)
     /// policy to reduce disk reads.
     ///
+    /// With `block_based` false, a full filter is built for each SST file,
+    /// which is what recent RocksDB versions use. The table options take
+    /// ownership of the policy, which lives as long as the options and the
+    /// databases opened with them.
+    ///
     /// # Examples
     ///
     /// ```
@@ -573,6 +693,9 @@ impl BlockBasedOptions {
     /// Ribbon filters use less memory in exchange for slightly more CPU usage
     /// compared to an equivalent bloom filter.
     ///
+    /// There is a single filter policy, so this replaces a filter set before
+    /// with `set_bloom_filter`, and the other way around.
+    ///
     /// # Examples
     ///
     /// ```
@@ -852,6 +975,36 @@ pub enum LogLevel {
     Header,
 }
 
+impl LogLevel {
+    fn from_c(level: c_uint) -> LogLevel {
+        match level {
+            0 => LogLevel::Debug,
+            1 => LogLevel::Info,
+            2 => LogLevel::Warn,
+            3 => LogLevel::Error,
+            4 => LogLevel::Fatal,
+            _ => LogLevel::Header,
+        }
+    }
+}
+
+/// The closure given to `Options::set_info_log_callback`, shared by the
+/// options and every database opened with them.
+pub(crate) struct InfoLogCallback(Box<dyn Fn(LogLevel, &str) + Send + Sync>);
+
+unsafe extern "C" fn info_log_callback(
+    raw_cb: *mut c_void,
+    level: c_uint,
+    msg: *mut c_char,
+    msg_length: size_t,
+) {
+    unsafe {
+        let cb = &*(raw_cb as *const InfoLogCallback);
+        let msg = slice::from_raw_parts(msg as *const u8, msg_length);
+        (cb.0)(LogLevel::from_c(level), &String::from_utf8_lossy(msg));
+    }
+}
+
 impl Options {
     /// By default, RocksDB uses only one background thread for flush and
     /// compaction. Calling this function will set it up such that total of
@@ -990,6 +1143,30 @@ impl Options {
         }
     }
 
+    /// If true, every SST file written by a flush or a compaction is read back
+    /// after being written, to check that the keys are in order and the
+    /// contents match what was written. This catches corruptions early at the
+    /// cost of extra reads.
+    ///
+    /// Default: false
+    pub fn set_paranoid_file_checks(&mut self, enabled: bool) {
+        unsafe {
+            ffi::rocksdb_options_set_paranoid_file_checks(self.inner, enabled as c_uchar);
+        }
+    }
+
+    /// If true, user keys and values may be included in error and corruption
+    /// messages, as well as in the info log, which helps finding the entry
+    /// behind a corruption. Leave it disabled when the data is sensitive, as
+    /// these messages usually end up in logs.
+    ///
+    /// Default: false
+    pub fn set_allow_data_in_errors(&mut self, enabled: bool) {
+        unsafe {
+            ffi::rocksdb_options_set_allow_data_in_errors(self.inner, enabled as c_uchar);
+        }
+    }
+
     /// A list of paths where SST files can be put into, with its target size.
     /// Newer data is placed into paths specified earlier in the vector while
     /// older data gradually moves to paths specified later in the vector.
@@ -1039,6 +1216,27 @@ impl Options {
         self.outlive.env = Some(env.clone());
     }
 
+    /// Attaches an `SstFileManager` which tracks the SST files of the DB and
+    /// rate limits their deletion.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckb_rocksdb::{Env, Options, SstFileManager};
+    ///
+    /// let manager = SstFileManager::new(&Env::default_env().unwrap());
+    /// manager.set_delete_rate_bytes_per_second(64 * 1024 * 1024);
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_sst_file_manager(&manager);
+    /// ```
+    pub fn set_sst_file_manager(&mut self, manager: &SstFileManager) {
+        unsafe {
+            ffi::rocksdb_options_set_sst_file_manager(self.inner, manager.0.inner);
+        }
+        self.outlive.sst_file_manager = Some(manager.clone());
+    }
+
     /// Sets the compression algorithm that will be used for compressing blocks.
     ///
     /// Default: `DBCompressionType::Snappy` (`DBCompressionType::None` if
@@ -1050,6 +1248,7 @@ impl Options {
     /// use ckb_rocksdb::{Options, DBCompressionType};
     ///
     /// let mut opts = Options::default();
+    /// # #[cfg(feature = "snappy")]
     /// opts.set_compression_type(DBCompressionType::Snappy);
     /// ```
     pub fn set_compression_type(&mut self, t: DBCompressionType) {
@@ -1073,6 +1272,7 @@ impl Options {
     /// use ckb_rocksdb::{Options, DBCompressionType};
     ///
     /// let mut opts = Options::default();
+    /// # #[cfg(feature = "zstd")]
     /// opts.set_bottommost_compression_type(DBCompressionType::Zstd);
     /// opts.set_bottommost_zstd_max_train_bytes(0, true);
     /// ```
@@ -1096,6 +1296,7 @@ impl Options {
     /// use ckb_rocksdb::{Options, DBCompressionType};
     ///
     /// let mut opts = Options::default();
+    /// # #[cfg(feature = "snappy")]
     /// opts.set_compression_per_level(&[
     ///     DBCompressionType::None,
     ///     DBCompressionType::None,
@@ -1169,6 +1370,7 @@ impl Options {
     /// use ckb_rocksdb::{Options, DBCompressionType};
     ///
     /// let mut opts = Options::default();
+    /// # #[cfg(feature = "zstd")]
     /// opts.set_bottommost_compression_type(DBCompressionType::Zstd);
     /// opts.set_bottommost_compression_options(4, 5, 6, 7, true);
     /// ```
@@ -1211,7 +1413,10 @@ impl Options {
     /// `max_dict_bytes` alone.
     ///
     /// The training data will be used to generate a dictionary of
-    /// `max_dict_bytes`.
+    /// `max_dict_bytes`, so a non-zero `max_dict_bytes` has to be set with
+    /// [`set_bottommost_compression_options`]. Training only happens when the
+    /// bottom-most compression type is `DBCompressionType::Zstd`, other
+    /// algorithms ignore it.
     ///
     /// Default: 0.
     pub fn set_bottommost_zstd_max_train_bytes(&mut self, value: c_int, enabled: bool) {
@@ -1273,6 +1478,14 @@ impl Options {
         }
     }
 
+    /// Sets a merge operator with separate full and partial merge functions.
+    ///
+    /// `full_merge_fn` folds the operands into the existing value, if any, on
+    /// reads and when compaction reaches a base value. `partial_merge_fn` is
+    /// called with `None` as existing value to combine operands into a single
+    /// one when there is no base value at hand, e.g. during a flush. It can
+    /// return `None` to signal that these operands can't be combined, which
+    /// keeps them as they are for a later full merge.
     pub fn set_merge_operator<F: MergeFn, PF: MergeFn>(
         &mut self,
         name: &str,
@@ -1326,13 +1539,13 @@ impl Options {
         });
 
         unsafe {
-            let cf = ffi::rocksdb_compactionfilter_create(
+            let cf = ffi::rocksdb_compactionfilterv2_create(
                 Box::into_raw(cb).cast::<c_void>(),
                 Some(compaction_filter::destructor_callback::<CompactionFilterCallback<F>>),
                 Some(compaction_filter::filter_callback::<CompactionFilterCallback<F>>),
                 Some(compaction_filter::name_callback::<CompactionFilterCallback<F>>),
             );
-            ffi::rocksdb_options_set_compaction_filter(self.inner, cf);
+            ffi::rocksdb_options_set_compaction_filter_v2(self.inner, cf);
         }
     }
 
@@ -1351,14 +1564,61 @@ impl Options {
         let factory = Box::new(factory);
 
         unsafe {
-            let cff = ffi::rocksdb_compactionfilterfactory_create(
+            let cff = ffi::rocksdb_compactionfilterfactoryv2_create(
                 Box::into_raw(factory).cast::<c_void>(),
                 Some(compaction_filter_factory::destructor_callback::<F>),
                 Some(compaction_filter_factory::create_compaction_filter_callback::<F>),
                 Some(compaction_filter_factory::name_callback::<F>),
             );
 
-            ffi::rocksdb_options_set_compaction_filter_factory(self.inner, cff);
+            ffi::rocksdb_options_set_compaction_filter_factory_v2(self.inner, cff);
+        }
+    }
+
+    /// Adds a listener notified about the flushes and compactions of the
+    /// databases opened with these options. Listeners accumulate, each call
+    /// adds one more.
+    ///
+    /// The listener is kept alive by the options and by every database opened
+    /// with them, and dropped once the last of them is gone.
+    pub fn add_event_listener<L>(&mut self, listener: L)
+    where
+        L: EventListener + 'static,
+    {
+        let listener = Box::new(listener);
+
+        unsafe {
+            let el = ffi::rocksdb_eventlistener_create(
+                Box::into_raw(listener).cast::<c_void>(),
+                Some(event_listener::destructor_callback::<L>),
+                Some(event_listener::on_flush_completed_callback::<L>),
+                Some(event_listener::on_compaction_completed_callback::<L>),
+            );
+            ffi::rocksdb_options_add_event_listener(self.inner, el);
+        }
+    }
+
+    /// Adds a factory of collectors gathering user properties about each
+    /// SST file written by the databases opened with these options. The
+    /// properties can then be used by a table filter, see
+    /// [ReadOptions::set_table_filter][set_table_filter]. Factories
+    /// accumulate, each call adds one more.
+    ///
+    /// [set_table_filter]: struct.ReadOptions.html#method.set_table_filter
+    pub fn add_table_properties_collector_factory<F>(&mut self, factory: F)
+    where
+        F: TablePropertiesCollectorFactory + 'static,
+    {
+        let factory = Box::new(factory);
+
+        unsafe {
+            let tpcf = ffi::rocksdb_tablepropertiescollectorfactory_create(
+                Box::into_raw(factory).cast::<c_void>(),
+                Some(table_properties::factory_destructor_callback::<F>),
+                Some(table_properties::factory_create_callback::<F>),
+                Some(table_properties::factory_name_callback::<F>),
+            );
+            ffi::rocksdb_options_add_table_properties_collector_factory(self.inner, tpcf);
         }
     }
 
@@ -1385,6 +1645,57 @@ impl Options {
         }
     }
 
+    /// Sets a comparator for keys carrying a user-defined timestamp of
+    /// `ts_size` bytes as suffix, which enables writing and reading keys at
+    /// a timestamp.
+    ///
+    /// `compare_fn` orders full keys, with newer timestamps first for equal
+    /// user keys, `compare_ts_fn` orders two timestamps, and
+    /// `compare_without_ts_fn` orders two keys ignoring their timestamps,
+    /// each key telling whether it has a timestamp suffix.
+    ///
+    /// As with `set_comparator`, the same comparator has to be used on
+    /// every open of the DB.
+    pub fn set_comparator_with_ts(
+        &mut self,
+        name: &str,
+        ts_size: usize,
+        compare_fn: CompareFn,
+        compare_ts_fn: CompareTsFn,
+        compare_without_ts_fn: CompareWithoutTsFn,
+    ) {
+        let cb = Box::new(ComparatorWithTsCallback {
+            name: CString::new(name.as_bytes()).unwrap(),
+            compare_fn,
+            compare_ts_fn,
+            compare_without_ts_fn,
+        });
+
+        unsafe {
+            let cmp = ffi::rocksdb_comparator_with_ts_create(
+                Box::into_raw(cb).cast::<c_void>(),
+                Some(comparator::destructor_with_ts_callback),
+                Some(comparator::compare_with_ts_callback),
+                Some(comparator::compare_ts_callback),
+                Some(comparator::compare_without_ts_callback),
+                Some(comparator::name_with_ts_callback),
+                ts_size,
+            );
+            ffi::rocksdb_options_set_comparator(self.inner, cmp);
+        }
+    }
+
+    /// Sets the prefix extractor used for prefix bloom filters and prefix
+    /// seeks. The options take ownership of the transform.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckb_rocksdb::{Options, SliceTransform};
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_prefix_extractor(SliceTransform::create_fixed_prefix(3));
+    /// ```
     pub fn set_prefix_extractor(&mut self, prefix_extractor: SliceTransform) {
         unsafe {
             ffi::rocksdb_options_set_prefix_extractor(self.inner, prefix_extractor.inner);
@@ -1477,6 +1788,15 @@ impl Options {
     /// If max_open_files is -1, DB will open all files on DB::Open(). You can
     /// use this option to increase the number of threads used to open the files.
     /// Default: 16
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckb_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_max_file_opening_threads(32);
+    /// ```
     pub fn set_max_file_opening_threads(&mut self, nthreads: c_int) {
         unsafe {
             ffi::rocksdb_options_set_max_file_opening_threads(self.inner, nthreads);
@@ -1536,6 +1856,51 @@ impl Options {
         unsafe {
             ffi::rocksdb_options_set_info_log_level(self.inner, level as c_int);
         }
+        // a logger filters on the level it was created with
+        if let Some(cb) = self.outlive.info_log.clone() {
+            self.set_info_log(level, cb);
+        }
+    }
+
+    /// Routes the info log to `cb` instead of the `LOG` file, e.g. to forward
+    /// it to an application logger. Only lines at `level` or above are passed
+    /// on; the level can be changed later with `set_log_level`.
+    ///
+    /// `cb` may be called from any thread, including background flush and
+    /// compaction threads. It is kept alive by the options and by every
+    /// database opened with them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckb_rocksdb::{Options, LogLevel};
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_info_log_callback(LogLevel::Warn, |level, msg| {
+    ///     eprintln!("rocksdb {:?}: {}", level, msg);
+    /// });
+    /// ```
+    pub fn set_info_log_callback<F>(&mut self, level: LogLevel, cb: F)
+    where
+        F: Fn(LogLevel, &str) + Send + Sync + 'static,
+    {
+        unsafe {
+            ffi::rocksdb_options_set_info_log_level(self.inner, level as c_int);
+        }
+        self.set_info_log(level, Arc::new(InfoLogCallback(Box::new(cb))));
+    }
+
+    fn set_info_log(&mut self, level: LogLevel, cb: Arc<InfoLogCallback>) {
+        unsafe {
+            let logger = ffi::rocksdb_logger_create_callback_logger(
+                level as c_int,
+                Some(info_log_callback),
+                Arc::as_ptr(&cb) as *mut c_void,
+            );
+            ffi::rocksdb_options_set_info_log(self.inner, logger);
+            ffi::rocksdb_logger_destroy(logger);
+        }
+        self.outlive.info_log = Some(cb);
     }
 
     /// Allows OS to incrementally sync files to disk while they are being
@@ -1762,6 +2127,15 @@ impl Options {
     /// by default files in different levels will have similar size.
     ///
     /// Dynamically changeable through SetOptions() API
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckb_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_target_file_size_multiplier(2);
+    /// ```
     pub fn set_target_file_size_multiplier(&mut self, multiplier: i32) {
         unsafe {
             ffi::rocksdb_options_set_target_file_size_multiplier(self.inner, multiplier as c_int);
@@ -1933,6 +2307,25 @@ impl Options {
         }
     }
 
+    /// Records the DB identity in the MANIFEST as well as in the `IDENTITY`
+    /// file, so the identity survives copying the DB without `IDENTITY`.
+    ///
+    /// Default: `true`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckb_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_write_dbid_to_manifest(true);
+    /// ```
+    pub fn set_write_dbid_to_manifest(&mut self, enabled: bool) {
+        unsafe {
+            ffi::rocksdb_options_set_write_dbid_to_manifest(self.inner, enabled as c_uchar);
+        }
+    }
+
     /// Sets the target file size for compaction.
     /// target_file_size_base is per-file size for level-1.
     /// Target file size for level L can be calculated by
@@ -2221,7 +2614,7 @@ impl Options {
 
     /// SetMemtableHugePageSize sets the page size for huge page for
     /// arena used by the memtable.
-    /// If <=0, it won't allocate from huge page but from malloc.
+    /// If 0, it won't allocate from huge page but from malloc.
     /// Users are responsible to reserve huge pages for it to be allocated. For
     /// example:
     ///      sysctl -w vm.nr_hugepages=20
@@ -2229,6 +2622,8 @@ impl Options {
     /// If there isn't enough free huge page available, it will fall back to
     /// malloc.
     ///
+    /// Default: 0
+    ///
     /// Dynamically changeable through SetOptions() API
     pub fn set_memtable_huge_page_size(&mut self, size: size_t) {
         unsafe {
@@ -2381,6 +2776,22 @@ impl Options {
         }
     }
 
+    /// If true, a separate write queue is maintained for the writes that only
+    /// go to the WAL, such as the prepare phase of a two-phase commit, so
+    /// they no longer wait behind memtable writes.
+    ///
+    /// This pairs with `set_unordered_write` under a TransactionDB with the
+    /// WRITE_PREPARED policy, and differs from `set_enable_pipelined_write`,
+    /// which splits every write across WAL and memtable queues; pipelined
+    /// write is not compatible with two write queues.
+    ///
+    /// Default: false
+    pub fn set_two_write_queues(&mut self, value: bool) {
+        unsafe {
+            ffi::rocksdb_options_set_two_write_queues(self.inner, value as c_uchar);
+        }
+    }
+
     /// Defines the underlying memtable implementation.
     /// See official [wiki](https://github.com/facebook/rocksdb/wiki/MemTable) for more information.
     /// Defaults to using a skiplist.
@@ -2582,7 +2993,29 @@ impl Options {
         }
     }
 
+    /// Returns the current value of a statistics ticker, or 0 when statistics
+    /// are not enabled with [`enable_statistics`](#method.enable_statistics).
+    ///
+    /// The counters are shared by all the databases opened with these options.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckb_rocksdb::{Options, Ticker};
+    ///
+    /// let mut opts = Options::default();
+    /// opts.enable_statistics();
+    /// assert_eq!(opts.get_ticker_count(Ticker::BlockCacheHit), 0);
+    /// ```
+    pub fn get_ticker_count(&self, ticker: Ticker) -> u64 {
+        let name = CString::new(ticker.name()).unwrap();
+        unsafe {
+            ffi::rocksdb_options_statistics_get_ticker_count_by_name(self.inner, name.as_ptr())
+        }
+    }
+
     /// If not zero, dump `rocksdb.stats` to LOG every `stats_dump_period_sec`.
+    /// 0 disables it.
     ///
     /// Default: `600` (10 mins)
     ///
@@ -2600,7 +3033,10 @@ impl Options {
         }
     }
 
-    /// If not zero, dump rocksdb.stats to RocksDB to LOG every `stats_persist_period_sec`.
+    /// If not zero, snapshot the statistics every `stats_persist_period_sec`
+    /// into the in-memory stats history, or into the hidden
+    /// `___rocksdb_stats_history___` column family when stats are persisted
+    /// to disk. 0 disables it.
     ///
     /// Default: `600` (10 mins)
     ///
@@ -2629,6 +3065,27 @@ impl Options {
         }
     }
 
+    /// If true, the cleanup of obsolete files and of the resources pinned by
+    /// iterators is scheduled on a background thread instead of running
+    /// inline, so dropping an iterator or finishing a flush does not block
+    /// the calling thread on file deletion.
+    ///
+    /// Default: `false`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckb_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_avoid_unnecessary_blocking_io(true);
+    /// ```
+    pub fn set_avoid_unnecessary_blocking_io(&mut self, val: bool) {
+        unsafe {
+            ffi::rocksdb_options_set_avoid_unnecessary_blocking_io(self.inner, val as c_uchar);
+        }
+    }
+
     /// Enable/disable adaptive mutex, which spins in the user space before resorting to kernel.
     ///
     /// This could reduce context switch when the mutex is not
@@ -2684,6 +3141,21 @@ impl Options {
         }
     }
 
+    /// Sets for how many seconds the write time of data is tracked, through a
+    /// mapping from sequence numbers to write times kept in the SST files.
+    ///
+    /// The tracked times let compactions reason about the age of the data,
+    /// which time-based tiering (e.g. placing data younger than a threshold
+    /// in the hot tier) builds on. It only preserves the information, it
+    /// doesn't change where data is placed by itself.
+    ///
+    /// Default: 0 (disabled)
+    pub fn set_preserve_internal_time_seconds(&mut self, secs: u64) {
+        unsafe {
+            ffi::rocksdb_options_set_preserve_internal_time_seconds(self.inner, secs);
+        }
+    }
+
     /// Specifies the absolute path of the directory the
     /// write-ahead log (WAL) should be written to.
     ///
@@ -2743,6 +3215,15 @@ impl Options {
     /// Default is 4MB, which is reasonable to reduce random IO
     /// as well as prevent overallocation for mounts that preallocate
     /// large amounts of data (such as xfs's allocsize option).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use ckb_rocksdb::Options;
+    ///
+    /// let mut opts = Options::default();
+    /// opts.set_manifest_preallocation_size(8 * 1024 * 1024);
+    /// ```
     pub fn set_manifest_preallocation_size(&mut self, size: usize) {
         unsafe {
             ffi::rocksdb_options_set_manifest_preallocation_size(self.inner, size);
@@ -3082,13 +3563,44 @@ impl WriteOptions {
     /// and the write may got lost after a crash.
     ///
     /// Default: false
-    pub fn disable_wal(&mut self, disable: bool) {
+    pub fn set_disable_wal(&mut self, disable: bool) {
         unsafe {
             ffi::rocksdb_writeoptions_disable_WAL(self.inner, disable as c_int);
         }
         self.option_disable_wal = Some(disable);
     }
 
+    /// Same as [`set_disable_wal`](#method.set_disable_wal).
+    pub fn disable_wal(&mut self, disable: bool) {
+        self.set_disable_wal(disable);
+    }
+
+    /// If true and we need to wait or sleep for the write request, fails
+    /// immediately with an `Incomplete` error instead of being slowed down
+    /// by write stalls.
+    ///
+    /// Default: false
+    pub fn set_no_slowdown(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_writeoptions_set_no_slowdown(self.inner, c_uchar::from(v));
+        }
+        self.option_set_no_slowdown = Some(v);
+    }
+
+    /// If true, this write request is of lower priority if compaction is
+    /// behind. In that case, if `no_slowdown` is true, the request will be
+    /// cancelled immediately with an `Incomplete` error returned. Otherwise,
+    /// it will be slowed down. The slowdown value is determined by RocksDB to
+    /// guarantee it introduces minimum impacts to high priority writes.
+    ///
+    /// Default: false
+    pub fn set_low_pri(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_writeoptions_set_low_pri(self.inner, c_uchar::from(v));
+        }
+        self.option_set_low_pri = Some(v);
+    }
+
     pub(crate) fn input_or_default(
         input: Option<&WriteOptions>,
         default_writeopts: &mut Option<WriteOptions>,
@@ -3115,6 +3627,8 @@ impl Default for WriteOptions {
         WriteOptions {
             option_set_sync: None,
             option_disable_wal: None,
+            option_set_no_slowdown: None,
+            option_set_low_pri: None,
             inner: write_opts,
         }
     }
@@ -3127,7 +3641,13 @@ impl Clone for WriteOptions {
             ops.set_sync(set_sync);
         };
         if let Some(disable_wal) = self.option_disable_wal {
-            ops.disable_wal(disable_wal);
+            ops.set_disable_wal(disable_wal);
+        };
+        if let Some(no_slowdown) = self.option_set_no_slowdown {
+            ops.set_no_slowdown(no_slowdown);
+        };
+        if let Some(low_pri) = self.option_set_low_pri {
+            ops.set_low_pri(low_pri);
         };
         ops
     }
@@ -3150,18 +3670,52 @@ impl ReadOptions {
         self.option_fill_cache = Some(v);
     }
 
-    /// Sets the snapshot which should be used for the read.
+    /// Sets the snapshot which should be used for the read, one of `Snapshot`,
+    /// `TransactionSnapshot` or `OptimisticTransactionSnapshot`.
     /// The snapshot must belong to the DB that is being read and must
-    /// not have been released.
+    /// not have been released. Clones of these read options keep it.
     pub fn set_snapshot<T>(&mut self, snapshot: &T)
     where
         T: ConstHandle<ffi::rocksdb_snapshot_t>,
     {
+        self.set_snapshot_raw(snapshot.const_handle());
+    }
+
+    fn set_snapshot_raw(&mut self, snapshot: *const ffi::rocksdb_snapshot_t) {
+        unsafe {
+            ffi::rocksdb_readoptions_set_snapshot(self.inner, snapshot);
+        }
+        self.option_set_snapshot = Some(snapshot);
+    }
+
+    /// Sets the timestamp to read at, for column families using a comparator
+    /// with timestamps: a read returns the newest version of a key whose
+    /// timestamp is not greater than `ts`.
+    pub fn set_timestamp<T: AsRef<[u8]>>(&mut self, ts: T) {
+        self.option_set_timestamp = Some(ts.as_ref().to_vec());
+        let ts = self.option_set_timestamp.as_ref().unwrap();
         unsafe {
-            ffi::rocksdb_readoptions_set_snapshot(self.inner, snapshot.const_handle());
+            ffi::rocksdb_readoptions_set_timestamp(
+                self.inner,
+                ts.as_ptr() as *const c_char,
+                ts.len() as size_t,
+            );
         }
     }
 
+    /// Builder form of [`set_snapshot`](#method.set_snapshot), for making
+    /// reusable read options pinned to a `Snapshot`, `TransactionSnapshot`
+    /// or `OptimisticTransactionSnapshot`.
+    ///
+    /// The read options must not be used after the snapshot is released.
+    pub fn with_snapshot<T>(mut self, snapshot: &T) -> Self
+    where
+        T: ConstHandle<ffi::rocksdb_snapshot_t>,
+    {
+        self.set_snapshot(snapshot);
+        self
+    }
+
     /// Sets the upper bound for an iterator.
     /// The upper bound itself is not included on the iteration result.
     pub fn set_iterate_upper_bound<K: AsRef<[u8]>>(&mut self, key: K) {
@@ -3177,11 +3731,12 @@ impl ReadOptions {
     }
 
     /// Sets the lower bound for an iterator.
+    /// The lower bound itself is included on the iteration result.
     pub fn set_iterate_lower_bound<K: AsRef<[u8]>>(&mut self, key: K) {
         self.option_set_iterate_lower_bound = Some(key.as_ref().to_vec());
         let key = self.option_set_iterate_lower_bound.as_ref().unwrap();
         unsafe {
-            ffi::rocksdb_readoptions_set_iterate_upper_bound(
+            ffi::rocksdb_readoptions_set_iterate_lower_bound(
                 self.inner,
                 key.as_ptr() as *const c_char,
                 key.len() as size_t,
@@ -3209,6 +3764,12 @@ impl ReadOptions {
     /// If true when calling Get(), we also skip prefix bloom when reading from
     /// block based table. It provides a way to read existing data after
     /// changing implementation of prefix extractor.
+    ///
+    /// Set it for full scans of a column family which has a prefix
+    /// extractor, so that iteration doesn't stop or skip keys at prefix
+    /// boundaries.
+    ///
+    /// Default: false
     pub fn set_total_order_seek(&mut self, v: bool) {
         unsafe { ffi::rocksdb_readoptions_set_total_order_seek(self.inner, v as c_uchar) }
         self.option_set_total_order_seek = Some(v);
@@ -3232,6 +3793,53 @@ impl ReadOptions {
         self.option_set_readahead_size = Some(v);
     }
 
+    /// Specify to create a tailing iterator -- a special iterator that has a
+    /// view of the complete database (i.e. it can also be used to read newly
+    /// added data) and is optimized for sequential reads. It will return records
+    /// that were inserted into the database after the creation of the iterator.
+    ///
+    /// Default: false
+    pub fn set_tailing(&mut self, v: bool) {
+        unsafe {
+            ffi::rocksdb_readoptions_set_tailing(self.inner, c_uchar::from(v));
+        }
+        self.option_set_tailing = Some(v);
+    }
+
+    /// Sets a callback deciding, from the properties of a SST file, whether
+    /// the file should be read at all. Tables for which the callback returns
+    /// false are skipped by iterators, which lets scans avoid files that can
+    /// not contain relevant keys. Point lookups such as `get_opt` ignore the
+    /// filter and read all tables.
+    ///
+    /// Default: no filter, all tables are read
+    ///
+    /// ```
+    /// use ckb_rocksdb::ReadOptions;
+    ///
+    /// let mut opts = ReadOptions::default();
+    /// opts.set_table_filter(|props| props.num_entries() > 0);
+    /// ```
+    pub fn set_table_filter<F>(&mut self, filter: F)
+    where
+        F: Fn(&TableProperties<'_>) -> bool + Send + Sync + 'static,
+    {
+        self.set_table_filter_fn(Arc::new(filter));
+    }
+
+    fn set_table_filter_fn(&mut self, filter: TableFilterFn) {
+        let cb = Box::into_raw(Box::new(filter.clone()));
+        unsafe {
+            ffi::rocksdb_readoptions_set_table_filter(
+                self.inner,
+                cb as *mut c_void,
+                Some(table_properties::table_filter_destructor_callback),
+                Some(table_properties::table_filter_callback),
+            );
+        }
+        self.option_set_table_filter = Some(filter);
+    }
+
     /// Asynchronously prefetch some data.
     ///
     /// Used for sequential reads and internal automatic prefetching.
@@ -3281,6 +3889,10 @@ impl Default for ReadOptions {
                 option_set_prefix_same_as_start: None,
                 option_set_total_order_seek: None,
                 option_set_readahead_size: None,
+                option_set_tailing: None,
+                option_set_table_filter: None,
+                option_set_timestamp: None,
+                option_set_snapshot: None,
                 inner: ffi::rocksdb_readoptions_create(),
             }
         }
@@ -3308,6 +3920,18 @@ impl Clone for ReadOptions {
         if let Some(set_readahead_size) = self.option_set_readahead_size {
             ops.set_readahead_size(set_readahead_size)
         };
+        if let Some(set_tailing) = self.option_set_tailing {
+            ops.set_tailing(set_tailing);
+        };
+        if let Some(table_filter) = &self.option_set_table_filter {
+            ops.set_table_filter_fn(table_filter.clone());
+        };
+        if let Some(timestamp) = &self.option_set_timestamp {
+            ops.set_timestamp(timestamp);
+        };
+        if let Some(snapshot) = self.option_set_snapshot {
+            ops.set_snapshot_raw(snapshot);
+        };
         ops
     }
 }
@@ -3477,14 +4101,23 @@ pub struct PlainTableFactoryOptions {
     pub store_index_in_file: bool,
 }
 
+/// Compression algorithms. Only those enabled through the crate features
+/// (`snappy`, `zlib`, `bzip2`, `lz4` and `zstd`) are available, as RocksDB
+/// refuses to open a DB configured with one which isn't compiled in.
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum DBCompressionType {
     None = ffi::rocksdb_no_compression as isize,
+    #[cfg(feature = "snappy")]
     Snappy = ffi::rocksdb_snappy_compression as isize,
+    #[cfg(feature = "zlib")]
     Zlib = ffi::rocksdb_zlib_compression as isize,
+    #[cfg(feature = "bzip2")]
     Bz2 = ffi::rocksdb_bz2_compression as isize,
+    #[cfg(feature = "lz4")]
     Lz4 = ffi::rocksdb_lz4_compression as isize,
+    #[cfg(feature = "lz4")]
     Lz4hc = ffi::rocksdb_lz4hc_compression as isize,
+    #[cfg(feature = "zstd")]
     Zstd = ffi::rocksdb_zstd_compression as isize,
 }
 
@@ -3503,6 +4136,73 @@ pub enum DBRecoveryMode {
     SkipAnyCorruptedRecord = ffi::rocksdb_skip_any_corrupted_records_recovery as isize,
 }
 
+/// Statistics counters which can be read with
+/// [`Options::get_ticker_count`](struct.Options.html#method.get_ticker_count).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Ticker {
+    BlockCacheMiss,
+    BlockCacheHit,
+    BlockCacheAdd,
+    BlockCacheIndexMiss,
+    BlockCacheIndexHit,
+    BlockCacheFilterMiss,
+    BlockCacheFilterHit,
+    BlockCacheDataMiss,
+    BlockCacheDataHit,
+    BlockCacheBytesRead,
+    BlockCacheBytesWrite,
+    BloomFilterUseful,
+    MemtableHit,
+    MemtableMiss,
+    NumberKeysWritten,
+    NumberKeysRead,
+    NumberDbSeek,
+    BytesWritten,
+    BytesRead,
+    CompactReadBytes,
+    CompactWriteBytes,
+    FlushWriteBytes,
+    CompactionKeyDropObsolete,
+    StallMicros,
+    WalFileSynced,
+    WalFileBytes,
+}
+
+impl Ticker {
+    /// Returns the name RocksDB uses for the ticker, as it appears in the
+    /// output of `Options::get_statistics`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Ticker::BlockCacheMiss => "rocksdb.block.cache.miss",
+            Ticker::BlockCacheHit => "rocksdb.block.cache.hit",
+            Ticker::BlockCacheAdd => "rocksdb.block.cache.add",
+            Ticker::BlockCacheIndexMiss => "rocksdb.block.cache.index.miss",
+            Ticker::BlockCacheIndexHit => "rocksdb.block.cache.index.hit",
+            Ticker::BlockCacheFilterMiss => "rocksdb.block.cache.filter.miss",
+            Ticker::BlockCacheFilterHit => "rocksdb.block.cache.filter.hit",
+            Ticker::BlockCacheDataMiss => "rocksdb.block.cache.data.miss",
+            Ticker::BlockCacheDataHit => "rocksdb.block.cache.data.hit",
+            Ticker::BlockCacheBytesRead => "rocksdb.block.cache.bytes.read",
+            Ticker::BlockCacheBytesWrite => "rocksdb.block.cache.bytes.write",
+            Ticker::BloomFilterUseful => "rocksdb.bloom.filter.useful",
+            Ticker::MemtableHit => "rocksdb.memtable.hit",
+            Ticker::MemtableMiss => "rocksdb.memtable.miss",
+            Ticker::NumberKeysWritten => "rocksdb.number.keys.written",
+            Ticker::NumberKeysRead => "rocksdb.number.keys.read",
+            Ticker::NumberDbSeek => "rocksdb.number.db.seek",
+            Ticker::BytesWritten => "rocksdb.bytes.written",
+            Ticker::BytesRead => "rocksdb.bytes.read",
+            Ticker::CompactReadBytes => "rocksdb.compact.read.bytes",
+            Ticker::CompactWriteBytes => "rocksdb.compact.write.bytes",
+            Ticker::FlushWriteBytes => "rocksdb.flush.write.bytes",
+            Ticker::CompactionKeyDropObsolete => "rocksdb.compaction.key.drop.obsolete",
+            Ticker::StallMicros => "rocksdb.stall.micros",
+            Ticker::WalFileSynced => "rocksdb.wal.synced",
+            Ticker::WalFileBytes => "rocksdb.wal.bytes",
+        }
+    }
+}
+
 pub struct FifoCompactOptions {
     pub(crate) inner: *mut ffi::rocksdb_fifo_compaction_options_t,
 }
@@ -3730,6 +4430,63 @@ impl CompactOptions {
     }
 }
 
+/// Options for explicit file-level compaction, see
+/// [`CompactFilesCF`](crate::ops::CompactFilesCF).
+pub struct CompactionOptions {
+    pub(crate) inner: *mut ffi::rocksdb_compactionoptions_t,
+}
+
+impl Default for CompactionOptions {
+    fn default() -> Self {
+        let opts = unsafe { ffi::rocksdb_compactionoptions_create() };
+        assert!(
+            !opts.is_null(),
+            "Could not create RocksDB Compaction Options"
+        );
+
+        Self { inner: opts }
+    }
+}
+
+impl Drop for CompactionOptions {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_compactionoptions_destroy(self.inner);
+        }
+    }
+}
+
+impl CompactionOptions {
+    /// Sets the compression algorithm used for the output files.
+    ///
+    /// Default: the compression configured for the column family
+    pub fn set_compression_type(&mut self, t: DBCompressionType) {
+        unsafe {
+            ffi::rocksdb_compactionoptions_set_compression(self.inner, t as c_int);
+        }
+    }
+
+    /// Sets the maximum size in bytes of each output file, compaction will
+    /// split the output into several files once this size is reached.
+    ///
+    /// Default: u64::MAX
+    pub fn set_output_file_size_limit(&mut self, limit: u64) {
+        unsafe {
+            ffi::rocksdb_compactionoptions_set_output_file_size_limit(self.inner, limit);
+        }
+    }
+
+    /// Sets the maximum number of threads used by this compaction, zero means
+    /// the `max_subcompactions` of the DB options is used.
+    ///
+    /// Default: 0
+    pub fn set_max_subcompactions(&mut self, num: u32) {
+        unsafe {
+            ffi::rocksdb_compactionoptions_set_max_subcompactions(self.inner, num);
+        }
+    }
+}
+
 /// Represents a path where sst files can be put into
 pub struct DBPath {
     pub(crate) inner: *mut ffi::rocksdb_dbpath_t,
@@ -16,20 +16,27 @@
 use libc::size_t;
 
 use crate::ffi;
+use std::cmp::Ordering;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::io;
 use std::marker::PhantomData;
 use std::ops::Deref;
 use std::slice;
 
-use crate::DB;
+use crate::{DB, DBValue};
 
 /// Wrapper around RocksDB PinnableSlice struct.
 ///
 /// With a pinnable slice, we can directly leverage in-memory data within
 /// RocksDB toa void unnecessary memory copies. The struct here wraps the
 /// returned raw pointer and ensures proper finalization work.
+///
+/// It also implements `io::Read` to feed the value to decoders. Reading moves
+/// an internal cursor but doesn't change the bytes seen through `Deref`.
 pub struct DBPinnableSlice<'a> {
     ptr: *mut ffi::rocksdb_pinnableslice_t,
+    pos: usize,
     db: PhantomData<&'a DB>,
 }
 
@@ -61,6 +68,56 @@ impl Deref for DBPinnableSlice<'_> {
     }
 }
 
+// Compared and hashed as the underlying bytes, like `[u8]`.
+impl PartialEq for DBPinnableSlice<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.deref() == other.deref()
+    }
+}
+
+impl Eq for DBPinnableSlice<'_> {}
+
+impl PartialOrd for DBPinnableSlice<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DBPinnableSlice<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.deref().cmp(other.deref())
+    }
+}
+
+impl Hash for DBPinnableSlice<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
+    }
+}
+
+impl io::Read for DBPinnableSlice<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut remaining = &self.deref()[self.pos..];
+        let n = remaining.read(buf)?;
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// Serialized as a byte string, like `DBVector`.
+#[cfg(feature = "serde1")]
+impl serde::Serialize for DBPinnableSlice<'_> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self)
+    }
+}
+
+impl From<DBPinnableSlice<'_>> for DBValue {
+    fn from(value: DBPinnableSlice<'_>) -> DBValue {
+        DBValue::from(value.as_ref())
+    }
+}
+
 impl Drop for DBPinnableSlice<'_> {
     fn drop(&mut self) {
         unsafe {
@@ -77,6 +134,7 @@ impl<'a> DBPinnableSlice<'a> {
     pub unsafe fn from_c(ptr: *mut ffi::rocksdb_pinnableslice_t) -> DBPinnableSlice<'a> {
         DBPinnableSlice {
             ptr,
+            pos: 0,
             db: PhantomData,
         }
     }
@@ -14,6 +14,9 @@
 //
 
 use libc::{self, c_void, size_t};
+use std::cmp::Ordering;
+use std::hash::{Hash, Hasher};
+use std::io;
 use std::ops::Deref;
 use std::slice;
 use std::str;
@@ -23,11 +26,18 @@ use std::str;
 /// This is a `C` allocated byte array and a length value.
 /// Normal usage would be to utilize the fact it implements `Deref<[u8]>` and use it as
 /// a slice.
+///
+/// It also implements `io::Read` to feed the value to decoders. Reading moves
+/// an internal cursor but doesn't change the bytes seen through `Deref`.
 pub struct DBVector {
     base: *mut u8,
     len: usize,
+    pos: usize,
 }
 
+// The bytes are owned by the vector, so it can be moved to another thread.
+unsafe impl Send for DBVector {}
+
 impl Deref for DBVector {
     type Target = [u8];
 
@@ -43,6 +53,51 @@ impl AsRef<[u8]> for DBVector {
     }
 }
 
+// Compared and hashed as the underlying bytes, like `[u8]`.
+impl PartialEq for DBVector {
+    fn eq(&self, other: &Self) -> bool {
+        self.deref() == other.deref()
+    }
+}
+
+impl Eq for DBVector {}
+
+impl PartialOrd for DBVector {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for DBVector {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.deref().cmp(other.deref())
+    }
+}
+
+impl Hash for DBVector {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.deref().hash(state)
+    }
+}
+
+impl io::Read for DBVector {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let mut remaining = &self.deref()[self.pos..];
+        let n = remaining.read(buf)?;
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+/// Serialized as a byte string. Since the bytes of a `DBVector` are owned by
+/// RocksDB, it deserializes into a `DBValue` instead.
+#[cfg(feature = "serde1")]
+impl serde::Serialize for DBVector {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(self)
+    }
+}
+
 impl Drop for DBVector {
     fn drop(&mut self) {
         unsafe {
@@ -70,6 +125,7 @@ impl DBVector {
         DBVector {
             base: val,
             len: val_len,
+            pos: 0,
         }
     }
 
@@ -80,3 +136,100 @@ impl DBVector {
         str::from_utf8(self.deref()).ok()
     }
 }
+
+/// Owned copy of a value read from the database, e.g. to keep it after the
+/// `DBVector` or `DBPinnableSlice` it came from is dropped.
+///
+/// Like `DBVector`, it dereferences to `[u8]` and is serialized as a byte
+/// string, but it can also be deserialized.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DBValue(Vec<u8>);
+
+impl DBValue {
+    /// Returns the underlying bytes.
+    pub fn into_vec(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl Deref for DBValue {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl AsRef<[u8]> for DBValue {
+    fn as_ref(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for DBValue {
+    fn from(value: Vec<u8>) -> DBValue {
+        DBValue(value)
+    }
+}
+
+impl From<&[u8]> for DBValue {
+    fn from(value: &[u8]) -> DBValue {
+        DBValue(value.to_vec())
+    }
+}
+
+impl From<DBVector> for DBValue {
+    fn from(value: DBVector) -> DBValue {
+        DBValue(value.to_vec())
+    }
+}
+
+impl From<DBValue> for Vec<u8> {
+    fn from(value: DBValue) -> Vec<u8> {
+        value.0
+    }
+}
+
+#[cfg(feature = "serde1")]
+impl serde::Serialize for DBValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_bytes(&self.0)
+    }
+}
+
+#[cfg(feature = "serde1")]
+impl<'de> serde::Deserialize<'de> for DBValue {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<DBValue, D::Error> {
+        struct BytesVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for BytesVisitor {
+            type Value = DBValue;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a byte string")
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, v: &[u8]) -> Result<DBValue, E> {
+                Ok(DBValue(v.to_vec()))
+            }
+
+            fn visit_byte_buf<E: serde::de::Error>(self, v: Vec<u8>) -> Result<DBValue, E> {
+                Ok(DBValue(v))
+            }
+
+            // for the formats writing bytes as a sequence, like JSON
+            fn visit_seq<A: serde::de::SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> Result<DBValue, A::Error> {
+                let mut bytes = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+                while let Some(byte) = seq.next_element()? {
+                    bytes.push(byte);
+                }
+                Ok(DBValue(bytes))
+            }
+        }
+
+        deserializer.deserialize_byte_buf(BytesVisitor)
+    }
+}
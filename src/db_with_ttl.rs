@@ -1,8 +1,8 @@
 use crate::ffi;
 use crate::ffi_util::to_cstring;
-use crate::ops::GetColumnFamilys;
+use crate::ops::{GetColumnFamilys, Open, OpenCF};
 use crate::{
-    ColumnFamily, Error, Options,
+    ColumnFamily, ColumnFamilyDescriptor, Error, Options,
     db_iterator::DBRawIterator,
     db_options::{OptionsMustOutliveDB, ReadOptions},
     handle::Handle,
@@ -26,6 +26,41 @@ impl DBWithTTL {
         self.path.as_path()
     }
 
+    /// Open a database whose entries expire `ttl` seconds after being written.
+    ///
+    /// Expired entries are only dropped during compaction, so reads may still
+    /// return them until the covering files have been compacted.
+    pub fn open_with_ttl<P: AsRef<Path>>(opts: &Options, path: P, ttl: i32) -> Result<Self, Error> {
+        Self::open_with_descriptor(opts, path, TTLOpenDescriptor::by_default(ttl))
+    }
+
+    /// Open a database with the given column families and a TTL per column family.
+    ///
+    /// `ttls` must hold one entry per name in `cfs`, in the same order. If `cfs`
+    /// does not name the `default` column family it is opened last, so its TTL
+    /// goes at the end of `ttls`.
+    pub fn open_cf_with_ttl<P, I, N>(
+        opts: &Options,
+        path: P,
+        cfs: I,
+        ttls: &[i32],
+    ) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = N>,
+        N: AsRef<str>,
+    {
+        let cfs = cfs
+            .into_iter()
+            .map(|name| ColumnFamilyDescriptor::new(name.as_ref(), Options::default()));
+        Self::open_cf_descriptors_with_descriptor(
+            opts,
+            path,
+            cfs,
+            TTLOpenDescriptor::by_columns(ttls.to_vec()),
+        )
+    }
+
     pub fn create_cf_with_ttl<N: AsRef<str>>(
         &mut self,
         name: N,
@@ -180,11 +215,12 @@ impl Handle<ffi::rocksdb_t> for DBWithTTL {
 }
 
 impl ops::Iterate for DBWithTTL {
-    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &'b ReadOptions) -> DBRawIterator<'b> {
         unsafe {
             DBRawIterator {
                 inner: ffi::rocksdb_create_iterator(self.inner, readopts.handle()),
                 db: PhantomData,
+                readopts: None,
             }
         }
     }
@@ -194,7 +230,7 @@ impl ops::IterateCF for DBWithTTL {
     fn get_raw_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBRawIterator<'b>, Error> {
         unsafe {
             Ok(DBRawIterator {
@@ -204,6 +240,7 @@ impl ops::IterateCF for DBWithTTL {
                     cf_handle.inner,
                 ),
                 db: PhantomData,
+                readopts: None,
             })
         }
     }
@@ -0,0 +1,132 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use crate::Error;
+
+use libc::{c_char, c_int, c_void, size_t};
+use std::ffi::CStr;
+use std::path::PathBuf;
+use std::slice;
+
+/// Details of a finished flush, passed to `EventListener::on_flush_completed`.
+#[derive(Debug, Clone)]
+pub struct FlushJobInfo {
+    /// The column family that was flushed.
+    pub cf_name: String,
+    /// The path of the table file the flush created.
+    pub file_path: PathBuf,
+    /// The smallest sequence number in the created file.
+    pub smallest_seqno: u64,
+    /// The largest sequence number in the created file.
+    pub largest_seqno: u64,
+}
+
+/// Details of a finished compaction, passed to `EventListener::on_compaction_completed`.
+#[derive(Debug, Clone)]
+pub struct CompactionJobInfo {
+    /// The column family that was compacted.
+    pub cf_name: String,
+    /// Whether the compaction succeeded.
+    pub status: Result<(), Error>,
+    /// The smallest level the input files were taken from.
+    pub base_input_level: i32,
+    /// The level the output files were written to.
+    pub output_level: i32,
+    pub num_input_files: usize,
+    pub num_output_files: usize,
+}
+
+/// Receives notifications about flushes and compactions of a database,
+/// see [Options::add_event_listener][add_event_listener].
+///
+/// The callbacks run on the background thread doing the work, possibly
+/// several at once, and block it until they return, so they should be quick.
+///
+/// [add_event_listener]: ../struct.Options.html#method.add_event_listener
+pub trait EventListener: Send + Sync {
+    /// Called after a flush has written its table file.
+    fn on_flush_completed(&self, _info: &FlushJobInfo) {}
+
+    /// Called after a compaction has finished, whether it succeeded or not.
+    fn on_compaction_completed(&self, _info: &CompactionJobInfo) {}
+}
+
+unsafe fn to_string(data: *const c_char, len: size_t) -> String {
+    unsafe { String::from_utf8_lossy(slice::from_raw_parts(data as *const u8, len)).into_owned() }
+}
+
+pub unsafe extern "C" fn destructor_callback<L>(raw_cb: *mut c_void)
+where
+    L: EventListener,
+{
+    unsafe {
+        let _ = Box::from_raw(raw_cb as *mut L);
+    }
+}
+
+pub unsafe extern "C" fn on_flush_completed_callback<L>(
+    raw_cb: *mut c_void,
+    cf_name: *const c_char,
+    cf_name_length: size_t,
+    file_path: *const c_char,
+    file_path_length: size_t,
+    smallest_seqno: u64,
+    largest_seqno: u64,
+) where
+    L: EventListener,
+{
+    unsafe {
+        let cb = &*(raw_cb as *const L);
+        cb.on_flush_completed(&FlushJobInfo {
+            cf_name: to_string(cf_name, cf_name_length),
+            file_path: to_string(file_path, file_path_length).into(),
+            smallest_seqno,
+            largest_seqno,
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub unsafe extern "C" fn on_compaction_completed_callback<L>(
+    raw_cb: *mut c_void,
+    cf_name: *const c_char,
+    cf_name_length: size_t,
+    errmsg: *const c_char,
+    base_input_level: c_int,
+    output_level: c_int,
+    num_input_files: size_t,
+    num_output_files: size_t,
+) where
+    L: EventListener,
+{
+    unsafe {
+        let cb = &*(raw_cb as *const L);
+        let status = if errmsg.is_null() {
+            Ok(())
+        } else {
+            Err(Error::new(
+                CStr::from_ptr(errmsg).to_string_lossy().into_owned(),
+            ))
+        };
+        cb.on_compaction_completed(&CompactionJobInfo {
+            cf_name: to_string(cf_name, cf_name_length),
+            status,
+            base_input_level,
+            output_level,
+            num_input_files,
+            num_output_files,
+        });
+    }
+}
@@ -78,6 +78,7 @@ mod db_options;
 mod db_pinnable_slice;
 mod db_vector;
 mod db_with_ttl;
+pub mod event_listener;
 mod handle;
 pub mod merge_operator;
 mod open_raw;
@@ -85,31 +86,38 @@ pub mod ops;
 mod optimistic_transaction;
 mod optimistic_transaction_db;
 mod options;
+#[cfg(feature = "perf")]
+pub mod perf;
 mod read_only_db;
 mod secondary_db;
 mod slice_transform;
 mod snapshot;
 mod sst_file_writer;
+mod table_properties;
 mod transaction;
 mod transaction_db;
+mod wal_iterator;
 mod write_batch;
+mod write_batch_with_index;
 
 pub mod prelude;
 
 pub use crate::column_family::ColumnFamilyDescriptor;
 pub use crate::compaction_filter::Decision as CompactionDecision;
 pub use crate::db::DB;
-pub use crate::db_iterator::{DBIterator, DBRawIterator, Direction, IteratorMode};
+pub use crate::db_iterator::{DBIterator, DBRawIterator, Direction, IteratorMode, OwnedDBIterator};
 pub use crate::db_options::{
     BlockBasedIndexType, BlockBasedOptions, BottommostLevelCompaction, Cache, CompactOptions,
-    CuckooTableOptions, DBCompactionStyle, DBCompressionType, DBPath, DBRecoveryMode,
-    DataBlockIndexType, Env, FifoCompactOptions, FlushOptions, IngestExternalFileOptions,
-    KeyEncodingType, LogLevel, MemtableFactory, Options, PlainTableFactoryOptions, ReadOptions,
-    UniversalCompactOptions, UniversalCompactionStopStyle, WriteOptions,
+    CompactionOptions, CuckooTableOptions, DBCompactionStyle, DBCompressionType, DBPath,
+    DBRecoveryMode, DataBlockIndexType, Env, FifoCompactOptions, FlushOptions,
+    IngestExternalFileOptions, KeyEncodingType, LogLevel, MemtableFactory, Options,
+    PlainTableFactoryOptions, ReadOptions, SstFileManager, Ticker, UniversalCompactOptions,
+    UniversalCompactionStopStyle, WriteOptions,
 };
 pub use crate::db_pinnable_slice::DBPinnableSlice;
-pub use crate::db_vector::DBVector;
+pub use crate::db_vector::{DBValue, DBVector};
 pub use crate::db_with_ttl::{DBWithTTL, TTLOpenDescriptor};
+pub use crate::event_listener::{CompactionJobInfo, EventListener, FlushJobInfo};
 pub use crate::handle::{ConstHandle, Handle};
 pub use crate::options::FullOptions;
 pub use crate::read_only_db::ReadOnlyDB;
@@ -117,8 +125,13 @@ pub use crate::secondary_db::{SecondaryDB, SecondaryOpenDescriptor};
 pub use crate::slice_transform::SliceTransform;
 pub use crate::snapshot::Snapshot;
 pub use crate::sst_file_writer::SstFileWriter;
+pub use crate::table_properties::{
+    TableProperties, TablePropertiesCollector, TablePropertiesCollectorFactory,
+};
 pub use crate::util::TemporaryDBPath;
-pub use crate::write_batch::WriteBatch;
+pub use crate::wal_iterator::WalIterator;
+pub use crate::write_batch::{CfWriteBatch, WriteBatch, WriteBatchIterator};
+pub use crate::write_batch_with_index::WriteBatchWithIndex;
 
 pub use crate::merge_operator::MergeOperands;
 use std::error;
@@ -129,16 +142,85 @@ pub use crate::optimistic_transaction_db::{OptimisticTransactionDB, OptimisticTr
 pub use crate::transaction::{Transaction, TransactionSnapshot};
 pub use crate::transaction_db::{TransactionDB, TransactionDBOptions, TransactionOptions};
 
+/// The kind of a RocksDB error, as reported by the status the error was
+/// created from.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ErrorKind {
+    NotFound,
+    Corruption,
+    NotSupported,
+    InvalidArgument,
+    IOError,
+    MergeInProgress,
+    Incomplete,
+    ShutdownInProgress,
+    /// The operation timed out, e.g. a pessimistic transaction waiting for
+    /// a lock longer than its lock timeout
+    TimedOut,
+    Aborted,
+    /// A resource is busy, e.g. a write conflict or a deadlock between
+    /// transactions
+    Busy,
+    Expired,
+    TryAgain,
+    CompactionTooLarge,
+    ColumnFamilyDropped,
+    Unknown,
+}
+
 /// A simple wrapper round a string, used for errors reported from
 /// ffi calls.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Error {
     message: String,
+    conflict_keys: Vec<Vec<u8>>,
 }
 
 impl Error {
     pub fn new(message: String) -> Error {
-        Error { message }
+        Error {
+            message,
+            conflict_keys: Vec::new(),
+        }
+    }
+
+    pub(crate) fn with_conflict_keys(mut self, conflict_keys: Vec<Vec<u8>>) -> Error {
+        self.conflict_keys = conflict_keys;
+        self
+    }
+
+    /// Returns the keys that caused an optimistic transaction commit to fail
+    /// with a write conflict.
+    ///
+    /// Empty for any other kind of error.
+    pub fn conflict_keys(&self) -> Vec<Vec<u8>> {
+        self.conflict_keys.clone()
+    }
+
+    /// Returns the kind of the error, parsed from the RocksDB status
+    /// message.
+    pub fn kind(&self) -> ErrorKind {
+        const PREFIXES: &[(&str, ErrorKind)] = &[
+            ("NotFound", ErrorKind::NotFound),
+            ("Corruption", ErrorKind::Corruption),
+            ("Not implemented", ErrorKind::NotSupported),
+            ("Invalid argument", ErrorKind::InvalidArgument),
+            ("IO error", ErrorKind::IOError),
+            ("Merge in progress", ErrorKind::MergeInProgress),
+            ("Result incomplete", ErrorKind::Incomplete),
+            ("Shutdown in progress", ErrorKind::ShutdownInProgress),
+            ("Operation timed out", ErrorKind::TimedOut),
+            ("Operation aborted", ErrorKind::Aborted),
+            ("Resource busy", ErrorKind::Busy),
+            ("Operation expired", ErrorKind::Expired),
+            ("Operation failed. Try again.", ErrorKind::TryAgain),
+            ("Compaction too large", ErrorKind::CompactionTooLarge),
+            ("Column family dropped", ErrorKind::ColumnFamilyDropped),
+        ];
+        PREFIXES
+            .iter()
+            .find(|(prefix, _)| self.message.starts_with(prefix))
+            .map_or(ErrorKind::Unknown, |&(_, kind)| kind)
     }
 
     pub fn into_string(self) -> String {
@@ -177,3 +259,4 @@ pub struct ColumnFamily {
 }
 
 unsafe impl Send for ColumnFamily {}
+unsafe impl Sync for ColumnFamily {}
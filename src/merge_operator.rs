@@ -197,13 +197,26 @@ impl MergeOperands {
             cursor: 0,
         }
     }
-}
 
-impl<'a> Iterator for &'a mut MergeOperands {
-    type Item = &'a [u8];
+    /// Returns the number of operands, regardless of how many were already
+    /// iterated over.
+    pub fn len(&self) -> usize {
+        self.num_operands
+    }
 
-    fn next(&mut self) -> Option<&'a [u8]> {
-        if self.cursor == self.num_operands {
+    pub fn is_empty(&self) -> bool {
+        self.num_operands == 0
+    }
+
+    /// Returns the operand at `index`, oldest first, without moving the iterator.
+    pub fn get(&self, index: usize) -> Option<&[u8]> {
+        unsafe { self.operand(index) }
+    }
+
+    // The operands are owned by RocksDB and outlive the merge callback, so
+    // the returned slice isn't tied to a borrow of `self`.
+    unsafe fn operand<'a>(&self, index: usize) -> Option<&'a [u8]> {
+        if index >= self.num_operands {
             None
         } else {
             unsafe {
@@ -211,14 +224,23 @@ impl<'a> Iterator for &'a mut MergeOperands {
                 let base_len = self.operands_list_len as usize;
                 let spacing = mem::size_of::<*const *const u8>();
                 let spacing_len = mem::size_of::<*const size_t>();
-                let len_ptr = (base_len + (spacing_len * self.cursor)) as *const size_t;
+                let len_ptr = (base_len + (spacing_len * index)) as *const size_t;
                 let len = *len_ptr;
-                let ptr = base + (spacing * self.cursor);
-                self.cursor += 1;
+                let ptr = base + (spacing * index);
                 Some(slice::from_raw_parts(*(ptr as *const *const u8), len))
             }
         }
     }
+}
+
+impl<'a> Iterator for &'a mut MergeOperands {
+    type Item = &'a [u8];
+
+    fn next(&mut self) -> Option<&'a [u8]> {
+        let operand = unsafe { self.operand(self.cursor) }?;
+        self.cursor += 1;
+        Some(operand)
+    }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         let remaining = self.num_operands - self.cursor;
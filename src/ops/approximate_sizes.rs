@@ -0,0 +1,98 @@
+use crate::ffi;
+use libc::{c_char, c_int, size_t};
+
+use crate::{ColumnFamily, Error, handle::Handle};
+
+pub trait GetApproximateSizes {
+    /// Estimates the on-disk size in bytes of each `[start, end)` key range
+    /// in the default column family. The returned sizes line up with
+    /// `ranges`. Data still in memtables is not counted.
+    fn get_approximate_sizes<S: AsRef<[u8]>, E: AsRef<[u8]>>(
+        &self,
+        ranges: &[(S, E)],
+    ) -> Result<Vec<u64>, Error>;
+}
+
+pub trait GetApproximateSizesCF {
+    fn get_approximate_sizes_cf_full<S: AsRef<[u8]>, E: AsRef<[u8]>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        ranges: &[(S, E)],
+    ) -> Result<Vec<u64>, Error>;
+
+    /// Estimates the on-disk size in bytes of each `[start, end)` key range
+    /// in a column family. The returned sizes line up with `ranges`. Data
+    /// still in memtables is not counted.
+    fn get_approximate_sizes_cf<S: AsRef<[u8]>, E: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        ranges: &[(S, E)],
+    ) -> Result<Vec<u64>, Error> {
+        self.get_approximate_sizes_cf_full(Some(cf), ranges)
+    }
+}
+
+impl<T> GetApproximateSizes for T
+where
+    T: GetApproximateSizesCF,
+{
+    fn get_approximate_sizes<S: AsRef<[u8]>, E: AsRef<[u8]>>(
+        &self,
+        ranges: &[(S, E)],
+    ) -> Result<Vec<u64>, Error> {
+        self.get_approximate_sizes_cf_full(None, ranges)
+    }
+}
+
+impl<T> GetApproximateSizesCF for T
+where
+    T: Handle<ffi::rocksdb_t> + super::Read,
+{
+    fn get_approximate_sizes_cf_full<S: AsRef<[u8]>, E: AsRef<[u8]>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        ranges: &[(S, E)],
+    ) -> Result<Vec<u64>, Error> {
+        let (start_keys, start_key_lens): (Vec<_>, Vec<_>) = ranges
+            .iter()
+            .map(|(start, _)| {
+                let start = start.as_ref();
+                (start.as_ptr() as *const c_char, start.len() as size_t)
+            })
+            .unzip();
+        let (end_keys, end_key_lens): (Vec<_>, Vec<_>) = ranges
+            .iter()
+            .map(|(_, end)| {
+                let end = end.as_ref();
+                (end.as_ptr() as *const c_char, end.len() as size_t)
+            })
+            .unzip();
+        let mut sizes = vec![0; ranges.len()];
+
+        unsafe {
+            match cf {
+                Some(cf) => ffi_try!(ffi::rocksdb_approximate_sizes_cf(
+                    self.handle(),
+                    cf.inner,
+                    ranges.len() as c_int,
+                    start_keys.as_ptr(),
+                    start_key_lens.as_ptr(),
+                    end_keys.as_ptr(),
+                    end_key_lens.as_ptr(),
+                    sizes.as_mut_ptr(),
+                )),
+                None => ffi_try!(ffi::rocksdb_approximate_sizes(
+                    self.handle(),
+                    ranges.len() as c_int,
+                    start_keys.as_ptr(),
+                    start_key_lens.as_ptr(),
+                    end_keys.as_ptr(),
+                    end_key_lens.as_ptr(),
+                    sizes.as_mut_ptr(),
+                )),
+            }
+        }
+
+        Ok(sizes)
+    }
+}
@@ -16,10 +16,14 @@ pub trait GetColumnFamilys {
 }
 
 pub trait CreateCF {
+    /// Create a column family with the given options.
+    ///
+    /// The new handle is registered with the database, so `cf_handle(name)` finds it afterwards.
     fn create_cf<N: AsRef<str>>(&mut self, name: N, opts: &Options) -> Result<(), Error>;
 }
 
 pub trait DropCF {
+    /// Drop the column family and remove its handle from the database.
     fn drop_cf(&mut self, name: &str) -> Result<(), Error>;
 }
 
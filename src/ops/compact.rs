@@ -1,13 +1,35 @@
 use super::columnfamily::GetColumnFamilys;
-use crate::{ColumnFamily, ffi_util::opt_bytes_to_ptr, handle::Handle};
+use crate::{ColumnFamily, CompactOptions, ffi_util::opt_bytes_to_ptr, handle::Handle};
 use libc::size_t;
 
 pub trait CompactRange {
+    /// Compacts the keys in `[start, end]` of the default column family,
+    /// `None` leaving that side of the range open. Deleted and overwritten
+    /// entries in the range are dropped, reclaiming their space.
     fn compact_range<S: AsRef<[u8]>, E: AsRef<[u8]>>(&self, start: Option<S>, end: Option<E>);
+
+    /// Same as `compact_range` with custom `CompactOptions`, e.g. to force
+    /// the compaction of the bottommost level.
+    fn compact_range_opt<S: AsRef<[u8]>, E: AsRef<[u8]>>(
+        &self,
+        start: Option<S>,
+        end: Option<E>,
+        opts: &CompactOptions,
+    );
 }
 
 pub trait CompactRangeCF {
+    /// Same as `compact_range` for a given column family.
     fn compact_range_cf(&self, cf: &ColumnFamily, start: Option<&[u8]>, end: Option<&[u8]>);
+
+    /// Same as `compact_range_opt` for a given column family.
+    fn compact_range_cf_opt(
+        &self,
+        cf: &ColumnFamily,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        opts: &CompactOptions,
+    );
 }
 
 impl<T> CompactRange for T
@@ -28,6 +50,27 @@ where
             );
         }
     }
+
+    fn compact_range_opt<S: AsRef<[u8]>, E: AsRef<[u8]>>(
+        &self,
+        start: Option<S>,
+        end: Option<E>,
+        opts: &CompactOptions,
+    ) {
+        unsafe {
+            let start = start.as_ref().map(AsRef::as_ref);
+            let end = end.as_ref().map(AsRef::as_ref);
+
+            ffi::rocksdb_compact_range_opt(
+                self.handle(),
+                opts.inner,
+                opt_bytes_to_ptr(start),
+                start.map_or(0, |s| s.len()) as size_t,
+                opt_bytes_to_ptr(end),
+                end.map_or(0, |e| e.len()) as size_t,
+            );
+        }
+    }
 }
 
 impl<T> CompactRangeCF for T
@@ -46,4 +89,24 @@ where
             );
         }
     }
+
+    fn compact_range_cf_opt(
+        &self,
+        cf: &ColumnFamily,
+        start: Option<&[u8]>,
+        end: Option<&[u8]>,
+        opts: &CompactOptions,
+    ) {
+        unsafe {
+            ffi::rocksdb_compact_range_cf_opt(
+                self.handle(),
+                cf.inner,
+                opts.inner,
+                opt_bytes_to_ptr(start),
+                start.map_or(0, |s| s.len()) as size_t,
+                opt_bytes_to_ptr(end),
+                end.map_or(0, |e| e.len()) as size_t,
+            );
+        }
+    }
 }
@@ -0,0 +1,98 @@
+use crate::{ColumnFamily, CompactionOptions, Error, ffi_util::to_cstring, handle::Handle};
+use libc::{c_char, c_int, size_t};
+use std::ffi::CString;
+
+pub trait CompactFiles {
+    /// Compacts the given SST files of the default column family into
+    /// `output_level`.
+    ///
+    /// File names are the ones reported by [`LiveFiles`](super::LiveFiles).
+    fn compact_files<N: AsRef<str>>(
+        &self,
+        file_names: &[N],
+        output_level: i32,
+        opts: &CompactionOptions,
+    ) -> Result<(), Error>;
+}
+
+pub trait CompactFilesCF {
+    fn compact_files_cf_full<N: AsRef<str>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        file_names: &[N],
+        output_level: i32,
+        opts: &CompactionOptions,
+    ) -> Result<(), Error>;
+
+    /// Compacts the given SST files of a column family into `output_level`.
+    ///
+    /// File names are the ones reported by [`LiveFiles`](super::LiveFiles).
+    fn compact_files_cf<N: AsRef<str>>(
+        &self,
+        cf: &ColumnFamily,
+        file_names: &[N],
+        output_level: i32,
+        opts: &CompactionOptions,
+    ) -> Result<(), Error> {
+        self.compact_files_cf_full(Some(cf), file_names, output_level, opts)
+    }
+}
+
+impl<T> CompactFiles for T
+where
+    T: CompactFilesCF,
+{
+    fn compact_files<N: AsRef<str>>(
+        &self,
+        file_names: &[N],
+        output_level: i32,
+        opts: &CompactionOptions,
+    ) -> Result<(), Error> {
+        self.compact_files_cf_full(None, file_names, output_level, opts)
+    }
+}
+
+impl<T> CompactFilesCF for T
+where
+    T: Handle<ffi::rocksdb_t> + super::Write,
+{
+    fn compact_files_cf_full<N: AsRef<str>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        file_names: &[N],
+        output_level: i32,
+        opts: &CompactionOptions,
+    ) -> Result<(), Error> {
+        let cnames = file_names
+            .iter()
+            .map(|name| {
+                to_cstring(
+                    name,
+                    "Failed to convert file name to CString when compacting files",
+                )
+            })
+            .collect::<Result<Vec<CString>, Error>>()?;
+        let cname_ptrs: Vec<*const c_char> = cnames.iter().map(|name| name.as_ptr()).collect();
+
+        unsafe {
+            match cf {
+                Some(cf) => ffi_try!(ffi::rocksdb_compact_files_cf(
+                    self.handle(),
+                    opts.inner,
+                    cf.inner,
+                    cname_ptrs.as_ptr(),
+                    cname_ptrs.len() as size_t,
+                    output_level as c_int,
+                )),
+                None => ffi_try!(ffi::rocksdb_compact_files(
+                    self.handle(),
+                    opts.inner,
+                    cname_ptrs.as_ptr(),
+                    cname_ptrs.len() as size_t,
+                    output_level as c_int,
+                )),
+            }
+        }
+        Ok(())
+    }
+}
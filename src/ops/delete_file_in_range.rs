@@ -0,0 +1,82 @@
+use crate::ffi;
+use libc::{c_char, size_t};
+
+use crate::{ColumnFamily, Error, handle::Handle};
+
+pub trait DeleteFileInRange {
+    /// Deletes the SST files of the default column family whose keys are
+    /// all within `[from, to]`. See
+    /// [`delete_file_in_range_cf`](trait.DeleteFileInRangeCF.html#method.delete_file_in_range_cf).
+    fn delete_file_in_range<K: AsRef<[u8]>>(&self, from: K, to: K) -> Result<(), Error>;
+}
+
+pub trait DeleteFileInRangeCF {
+    fn delete_file_in_range_cf_full<K: AsRef<[u8]>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        from: K,
+        to: K,
+    ) -> Result<(), Error>;
+
+    /// Deletes the SST files of a column family whose keys are all within
+    /// `[from, to]`.
+    ///
+    /// Unlike `compact_range_cf`, whole files are dropped immediately
+    /// without rewriting anything. Files only partly covered by the range
+    /// are kept, and memtables are left untouched, so some keys of the
+    /// range may survive.
+    fn delete_file_in_range_cf<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        from: K,
+        to: K,
+    ) -> Result<(), Error> {
+        self.delete_file_in_range_cf_full(Some(cf), from, to)
+    }
+}
+
+impl<T> DeleteFileInRange for T
+where
+    T: DeleteFileInRangeCF,
+{
+    fn delete_file_in_range<K: AsRef<[u8]>>(&self, from: K, to: K) -> Result<(), Error> {
+        self.delete_file_in_range_cf_full(None, from, to)
+    }
+}
+
+impl<T> DeleteFileInRangeCF for T
+where
+    T: Handle<ffi::rocksdb_t> + super::Write,
+{
+    fn delete_file_in_range_cf_full<K: AsRef<[u8]>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        from: K,
+        to: K,
+    ) -> Result<(), Error> {
+        let from = from.as_ref();
+        let to = to.as_ref();
+
+        unsafe {
+            match cf {
+                Some(cf) => ffi_try!(ffi::rocksdb_delete_file_in_range_cf(
+                    self.handle(),
+                    cf.inner,
+                    from.as_ptr() as *const c_char,
+                    from.len() as size_t,
+                    to.as_ptr() as *const c_char,
+                    to.len() as size_t,
+                )),
+                None => ffi_try!(ffi::rocksdb_delete_file_in_range(
+                    self.handle(),
+                    from.as_ptr() as *const c_char,
+                    from.len() as size_t,
+                    to.as_ptr() as *const c_char,
+                    to.len() as size_t,
+                )),
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,8 +1,12 @@
+use super::property::GetPropertyCF;
 use crate::ffi;
-use crate::{ColumnFamily, Error, FlushOptions, handle::Handle};
+use crate::{ColumnFamily, Error, FlushOptions, ffi_util::opt_bytes_to_ptr, handle::Handle};
+use libc::size_t;
+use std::thread;
+use std::time::Duration;
 
 pub trait Flush {
-    //// Flushes database memtables to SST files on the disk.
+    /// Flushes database memtables to SST files on the disk.
     fn flush_opt(&self, flushopts: &FlushOptions) -> Result<(), Error>;
 
     /// Flushes database memtables to SST files on the disk using default options.
@@ -11,7 +15,6 @@ pub trait Flush {
     }
 }
 
-#[allow(dead_code)]
 pub trait FlushCF {
     /// Flushes database memtables to SST files on the disk for a given column family.
     fn flush_cf_opt(&self, cf: &ColumnFamily, flushopts: &FlushOptions) -> Result<(), Error>;
@@ -21,6 +24,29 @@ pub trait FlushCF {
     fn flush_cf(&self, cf: &ColumnFamily) -> Result<(), Error> {
         self.flush_cf_opt(cf, &FlushOptions::default())
     }
+
+    /// Flushes the memtables of a column family and blocks until the
+    /// `rocksdb.mem-table-flush-pending` property of that column family
+    /// reads 0, so the data written before the call is in SST files.
+    fn wait_memtable_flush_cf(&self, cf: &ColumnFamily) -> Result<(), Error>;
+}
+
+pub trait FlushRangeCF {
+    /// Persists the given key range of a column family to SST files.
+    ///
+    /// RocksDB can not flush part of a memtable, so the whole memtable of
+    /// the column family is flushed first, which puts `[from, to)` on disk.
+    /// The SST files overlapping `[from, to)` are then marked for compaction,
+    /// so that background compaction pushes that range to lower levels
+    /// without the cost of a manual `compact_range_cf` over it.
+    ///
+    /// `None` means the range is unbounded on that side.
+    fn flush_range_cf(
+        &self,
+        cf: &ColumnFamily,
+        from: Option<&[u8]>,
+        to: Option<&[u8]>,
+    ) -> Result<(), Error>;
 }
 
 impl<T> Flush for T
@@ -49,4 +75,39 @@ where
         }
         Ok(())
     }
+
+    fn wait_memtable_flush_cf(&self, cf: &ColumnFamily) -> Result<(), Error> {
+        let mut flushopts = FlushOptions::default();
+        flushopts.set_wait(true);
+        self.flush_cf_opt(cf, &flushopts)?;
+        while self.mem_table_flush_pending_cf(cf)? {
+            thread::sleep(Duration::from_millis(1));
+        }
+        Ok(())
+    }
+}
+
+impl<T> FlushRangeCF for T
+where
+    T: Handle<ffi::rocksdb_t> + super::Write,
+{
+    fn flush_range_cf(
+        &self,
+        cf: &ColumnFamily,
+        from: Option<&[u8]>,
+        to: Option<&[u8]>,
+    ) -> Result<(), Error> {
+        self.flush_cf(cf)?;
+        unsafe {
+            ffi_try!(ffi::rocksdb_suggest_compact_range_cf(
+                self.handle(),
+                cf.inner,
+                opt_bytes_to_ptr(from),
+                from.map_or(0, |s| s.len()) as size_t,
+                opt_bytes_to_ptr(to),
+                to.map_or(0, |e| e.len()) as size_t,
+            ));
+        }
+        Ok(())
+    }
 }
@@ -14,7 +14,7 @@
 //
 
 use crate::ffi;
-use libc::{c_char, size_t};
+use libc::{c_char, c_void, size_t};
 
 use crate::{ColumnFamily, DBVector, Error, ReadOptions, handle::Handle};
 
@@ -117,3 +117,107 @@ where
         }
     }
 }
+
+pub trait GetWithTs {
+    /// Return the bytes associated with a key value together with the
+    /// timestamp they were written at. `readopts` has to carry the timestamp
+    /// to read at, see `ReadOptions::set_timestamp`.
+    fn get_with_ts<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> Result<Option<(DBVector, Vec<u8>)>, Error>;
+}
+
+pub trait GetWithTsCF {
+    fn get_cf_with_ts_full<K: AsRef<[u8]>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> Result<Option<(DBVector, Vec<u8>)>, Error>;
+
+    /// Return the bytes associated with a key value in a column family using
+    /// a comparator with timestamps, together with the timestamp they were
+    /// written at. `readopts` has to carry the timestamp to read at, see
+    /// `ReadOptions::set_timestamp`.
+    fn get_cf_with_ts<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> Result<Option<(DBVector, Vec<u8>)>, Error> {
+        self.get_cf_with_ts_full(Some(cf), key, readopts)
+    }
+}
+
+impl<T> GetWithTs for T
+where
+    T: GetWithTsCF,
+{
+    fn get_with_ts<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> Result<Option<(DBVector, Vec<u8>)>, Error> {
+        self.get_cf_with_ts_full(None, key, readopts)
+    }
+}
+
+impl<T> GetWithTsCF for T
+where
+    T: Handle<ffi::rocksdb_t> + super::Read,
+{
+    fn get_cf_with_ts_full<K: AsRef<[u8]>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        key: K,
+        readopts: &ReadOptions,
+    ) -> Result<Option<(DBVector, Vec<u8>)>, Error> {
+        let key = key.as_ref();
+        let key_ptr = key.as_ptr() as *const c_char;
+        let key_len = key.len() as size_t;
+
+        unsafe {
+            let mut val_len: size_t = 0;
+            let mut ts: *mut c_char = std::ptr::null_mut();
+            let mut ts_len: size_t = 0;
+
+            let val = match cf {
+                Some(cf) => ffi_try!(ffi::rocksdb_get_cf_with_ts(
+                    self.handle(),
+                    readopts.handle(),
+                    cf.handle(),
+                    key_ptr,
+                    key_len,
+                    &mut val_len,
+                    &mut ts,
+                    &mut ts_len,
+                )),
+                None => ffi_try!(ffi::rocksdb_get_with_ts(
+                    self.handle(),
+                    readopts.handle(),
+                    key_ptr,
+                    key_len,
+                    &mut val_len,
+                    &mut ts,
+                    &mut ts_len,
+                )),
+            } as *mut u8;
+
+            let timestamp = if ts.is_null() {
+                Vec::new()
+            } else {
+                let timestamp = std::slice::from_raw_parts(ts as *const u8, ts_len).to_vec();
+                ffi::rocksdb_free(ts as *mut c_void);
+                timestamp
+            };
+
+            if val.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some((DBVector::from_c(val, val_len), timestamp)))
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::ffi;
+use libc::{c_char, c_void, size_t};
+use std::ptr;
+use std::slice;
+
+use crate::{ColumnFamily, DBVector, Error, ReadOptions, handle::Handle};
+
+pub trait GetMergeOperands {
+    /// Returns the unmerged operands of a key in the default column family,
+    /// oldest first. A base value written with `put` is returned as the
+    /// first operand.
+    fn get_merge_operands<K: AsRef<[u8]>>(&self, key: K) -> Result<Vec<DBVector>, Error>;
+}
+
+pub trait GetMergeOperandsCF {
+    fn get_merge_operands_cf_full<K: AsRef<[u8]>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        key: K,
+        readopts: Option<&ReadOptions>,
+    ) -> Result<Vec<DBVector>, Error>;
+
+    /// Returns the unmerged operands of a key in a column family, oldest
+    /// first. A base value written with `put` is returned as the first
+    /// operand.
+    fn get_merge_operands_cf<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        key: K,
+    ) -> Result<Vec<DBVector>, Error> {
+        self.get_merge_operands_cf_full(Some(cf), key, None)
+    }
+}
+
+impl<T> GetMergeOperands for T
+where
+    T: GetMergeOperandsCF,
+{
+    fn get_merge_operands<K: AsRef<[u8]>>(&self, key: K) -> Result<Vec<DBVector>, Error> {
+        self.get_merge_operands_cf_full(None, key, None)
+    }
+}
+
+impl<T> GetMergeOperandsCF for T
+where
+    T: Handle<ffi::rocksdb_t> + super::Read,
+{
+    fn get_merge_operands_cf_full<K: AsRef<[u8]>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        key: K,
+        readopts: Option<&ReadOptions>,
+    ) -> Result<Vec<DBVector>, Error> {
+        let mut default_readopts = None;
+
+        let ro_handle = ReadOptions::input_or_default(readopts, &mut default_readopts)?;
+
+        let key = key.as_ref();
+
+        unsafe {
+            let mut num_operands: size_t = 0;
+            let mut operands: *mut *mut c_char = ptr::null_mut();
+            let mut operand_lens: *mut size_t = ptr::null_mut();
+            ffi_try!(ffi::rocksdb_get_merge_operands_cf(
+                self.handle(),
+                ro_handle,
+                cf.map_or(ptr::null_mut(), |cf| cf.inner),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                &mut num_operands,
+                &mut operands,
+                &mut operand_lens,
+            ));
+
+            if operands.is_null() {
+                return Ok(Vec::new());
+            }
+
+            let values = slice::from_raw_parts(operands, num_operands)
+                .iter()
+                .zip(slice::from_raw_parts(operand_lens, num_operands))
+                .map(|(&value, &len)| DBVector::from_c(value as *mut u8, len))
+                .collect();
+            ffi::rocksdb_free(operands as *mut c_void);
+            ffi::rocksdb_free(operand_lens as *mut c_void);
+            Ok(values)
+        }
+    }
+}
@@ -80,6 +80,20 @@ pub trait GetPinnedCF<'a> {
     ) -> Result<Option<DBPinnableSlice<'a>>, Error> {
         self.get_pinned_cf_full(Some(cf), key, Some(readopts))
     }
+
+    /// Return the value associated with a key of a column family as a
+    /// pinned slice borrowed from RocksDB, or `None` if the key does not
+    /// exist.
+    ///
+    /// This is the canonical single-key pinned get, it behaves the same for
+    /// databases, transactions and snapshots.
+    fn try_get_cf<K: AsRef<[u8]>>(
+        &'a self,
+        cf: Self::ColumnFamily,
+        key: K,
+    ) -> Result<Option<DBPinnableSlice<'a>>, Error> {
+        self.get_pinned_cf_full(Some(cf), key, None)
+    }
 }
 
 impl<'a, T, R> GetPinned<'a> for T
@@ -114,32 +128,80 @@ where
 
         let ro_handle = ReadOptions::input_or_default(readopts, &mut default_readopts)?;
 
-        let key = key.as_ref();
+        unsafe { get_pinned_cf_raw(self.handle(), ro_handle, cf, key.as_ref()) }
+    }
+}
+
+/// The raw handles a pinned get can go through, the databases and the
+/// transactions.
+pub(crate) trait GetPinnedHandle: Copy {
+    unsafe fn get_pinned(
+        self,
+        ro_handle: *mut ffi::rocksdb_readoptions_t,
+        cf: Option<&ColumnFamily>,
+        key: &[u8],
+    ) -> Result<*mut ffi::rocksdb_pinnableslice_t, Error>;
+}
+
+impl GetPinnedHandle for *mut ffi::rocksdb_t {
+    unsafe fn get_pinned(
+        self,
+        ro_handle: *mut ffi::rocksdb_readoptions_t,
+        cf: Option<&ColumnFamily>,
+        key: &[u8],
+    ) -> Result<*mut ffi::rocksdb_pinnableslice_t, Error> {
         let key_ptr = key.as_ptr() as *const c_char;
         let key_len = key.len() as size_t;
 
         unsafe {
-            let val = match cf {
+            Ok(match cf {
                 Some(cf) => ffi_try!(ffi::rocksdb_get_pinned_cf(
-                    self.handle(),
-                    ro_handle,
-                    cf.inner,
-                    key_ptr,
-                    key_len,
+                    self, ro_handle, cf.inner, key_ptr, key_len
                 )),
-                None => ffi_try!(ffi::rocksdb_get_pinned(
-                    self.handle(),
-                    ro_handle,
-                    key_ptr,
-                    key_len,
+                None => ffi_try!(ffi::rocksdb_get_pinned(self, ro_handle, key_ptr, key_len)),
+            })
+        }
+    }
+}
+
+impl GetPinnedHandle for *mut ffi::rocksdb_transaction_t {
+    unsafe fn get_pinned(
+        self,
+        ro_handle: *mut ffi::rocksdb_readoptions_t,
+        cf: Option<&ColumnFamily>,
+        key: &[u8],
+    ) -> Result<*mut ffi::rocksdb_pinnableslice_t, Error> {
+        let key_ptr = key.as_ptr() as *const c_char;
+        let key_len = key.len() as size_t;
+
+        unsafe {
+            Ok(match cf {
+                Some(cf) => ffi_try!(ffi::rocksdb_transaction_get_pinned_cf(
+                    self, ro_handle, cf.inner, key_ptr, key_len
+                )),
+                None => ffi_try!(ffi::rocksdb_transaction_get_pinned(
+                    self, ro_handle, key_ptr, key_len
                 )),
-            };
+            })
+        }
+    }
+}
 
-            if val.is_null() {
-                Ok(None)
-            } else {
-                Ok(Some(DBPinnableSlice::from_c(val)))
-            }
+/// Pinned get shared by the databases, the transactions and their
+/// snapshots, `ro_handle` must stay valid for the duration of the call.
+pub(crate) unsafe fn get_pinned_cf_raw<'a, H: GetPinnedHandle>(
+    handle: H,
+    ro_handle: *mut ffi::rocksdb_readoptions_t,
+    cf: Option<&ColumnFamily>,
+    key: &[u8],
+) -> Result<Option<DBPinnableSlice<'a>>, Error> {
+    unsafe {
+        let val = handle.get_pinned(ro_handle, cf, key)?;
+
+        if val.is_null() {
+            Ok(None)
+        } else {
+            Ok(Some(DBPinnableSlice::from_c(val)))
         }
     }
 }
@@ -1,8 +1,10 @@
 use crate::ffi;
 use crate::ffi_util::to_cpath;
 use crate::{ColumnFamily, Error, IngestExternalFileOptions, handle::Handle};
+use libc::{c_char, size_t};
 use std::ffi::CString;
 use std::path::Path;
+use std::ptr;
 
 pub trait IngestExternalFile {
     fn ingest_external_file_full<P: AsRef<Path>>(
@@ -45,7 +47,7 @@ pub trait IngestExternalFileCF {
     }
 
     /// Loads a list of external SST files created with SstFileWriter into the DB for given Column Family
-    fn ingest_external_file_opts<P: AsRef<Path>>(
+    fn ingest_external_file_cf_opts<P: AsRef<Path>>(
         &self,
         cf: &ColumnFamily,
         paths: Vec<P>,
@@ -115,3 +117,94 @@ where
         }
     }
 }
+
+pub trait IngestSstBytes {
+    /// Loads a SST file created with SstFileWriter from memory into the DB.
+    ///
+    /// RocksDB can only ingest files, so the bytes are first written to a
+    /// file in `staging_dir` through the env of the DB. For a DB on disk this
+    /// is a temporary on-disk copy of `data`, so `staging_dir` needs room for
+    /// it and is best on the filesystem of the DB, letting the file be moved
+    /// rather than copied into it. Only a DB using `Env::mem_env` keeps the
+    /// whole ingestion in memory. The staged file is removed once the
+    /// ingestion is done; keeping `staging_dir` out of the DB directory
+    /// avoids leaving it among the DB files if the process dies before that.
+    fn ingest_sst_bytes<P: AsRef<Path>>(
+        &self,
+        data: &[u8],
+        staging_dir: P,
+        opts: &IngestExternalFileOptions,
+    ) -> Result<(), Error>;
+}
+
+pub trait IngestSstBytesCF {
+    fn ingest_sst_bytes_cf_full<P: AsRef<Path>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        data: &[u8],
+        staging_dir: P,
+        opts: Option<&IngestExternalFileOptions>,
+    ) -> Result<(), Error>;
+
+    /// Loads a SST file created with SstFileWriter from memory into the DB
+    /// for given Column Family, see `IngestSstBytes::ingest_sst_bytes`.
+    fn ingest_sst_bytes_cf<P: AsRef<Path>>(
+        &self,
+        cf: &ColumnFamily,
+        data: &[u8],
+        staging_dir: P,
+        opts: &IngestExternalFileOptions,
+    ) -> Result<(), Error> {
+        self.ingest_sst_bytes_cf_full(Some(cf), data, staging_dir, Some(opts))
+    }
+}
+
+impl<T> IngestSstBytes for T
+where
+    T: IngestSstBytesCF,
+{
+    fn ingest_sst_bytes<P: AsRef<Path>>(
+        &self,
+        data: &[u8],
+        staging_dir: P,
+        opts: &IngestExternalFileOptions,
+    ) -> Result<(), Error> {
+        self.ingest_sst_bytes_cf_full(None, data, staging_dir, Some(opts))
+    }
+}
+
+impl<T> IngestSstBytesCF for T
+where
+    T: Handle<ffi::rocksdb_t> + super::Write,
+{
+    fn ingest_sst_bytes_cf_full<P: AsRef<Path>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        data: &[u8],
+        staging_dir: P,
+        opts: Option<&IngestExternalFileOptions>,
+    ) -> Result<(), Error> {
+        let mut default_opts = None;
+
+        let ief_handle = IngestExternalFileOptions::input_or_default(opts, &mut default_opts)?;
+
+        let cf_handle = cf.map_or(ptr::null_mut(), |cf| cf.handle());
+        let staging_dir = to_cpath(
+            staging_dir,
+            "Failed to convert path to CString when IngestSstBytes.",
+        )?;
+
+        unsafe {
+            ffi_try!(ffi::rocksdb_ingest_external_file_bytes_cf(
+                self.handle(),
+                cf_handle,
+                staging_dir.as_ptr(),
+                data.as_ptr() as *const c_char,
+                data.len() as size_t,
+                ief_handle
+            ));
+
+            Ok(())
+        }
+    }
+}
@@ -1,33 +1,34 @@
 use crate::{ColumnFamily, DBIterator, DBRawIterator, Direction, Error, IteratorMode, ReadOptions};
 
 pub trait Iterate {
-    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b>;
+    /// Opens a raw iterator using the provided ReadOptions.
+    ///
+    /// RocksDB does not copy the iterate bounds, so the ReadOptions, which
+    /// own the bound buffers, must outlive the iterator.
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &'b ReadOptions) -> DBRawIterator<'b>;
 
     fn get_iter<'a: 'b, 'b>(
         &'a self,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
         mode: IteratorMode<'_>,
     ) -> DBIterator<'b> {
-        let mut rv = DBIterator {
-            raw: self.get_raw_iter(readopts),
-            direction: Direction::Forward, // blown away by set_mode()
-            just_seeked: false,
-        };
-        rv.set_mode(mode);
-        rv
+        DBIterator::new(self.get_raw_iter(readopts), mode)
     }
 
+    /// Opens an iterator using the provided ReadOptions.
+    ///
+    /// RocksDB does not copy the iterate bounds, so the ReadOptions, which
+    /// own the bound buffers, must outlive the iterator.
     fn iterator_opt<'a: 'b, 'b>(
         &'a self,
         mode: IteratorMode<'_>,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> DBIterator<'b> {
         self.get_iter(readopts, mode)
     }
 
     fn iterator<'a: 'b, 'b>(&'a self, mode: IteratorMode<'_>) -> DBIterator<'b> {
-        let readopts = ReadOptions::default();
-        self.iterator_opt(mode, &readopts)
+        DBIterator::new(owned_raw_iter(self, ReadOptions::default()), mode)
     }
 
     /// Opens an interator with `set_total_order_seek` enabled.
@@ -36,50 +37,74 @@ pub trait Iterate {
     fn full_iterator<'a: 'b, 'b>(&'a self, mode: IteratorMode<'_>) -> DBIterator<'b> {
         let mut opts = ReadOptions::default();
         opts.set_total_order_seek(true);
-        self.get_iter(&opts, mode)
+        DBIterator::new(owned_raw_iter(self, opts), mode)
     }
 
     fn prefix_iterator<'a: 'b, 'b>(&'a self, prefix: &[u8]) -> DBIterator<'b> {
         let mut opts = ReadOptions::default();
         opts.set_prefix_same_as_start(true);
-        self.get_iter(&opts, IteratorMode::From(prefix, Direction::Forward))
+        DBIterator::new(
+            owned_raw_iter(self, opts),
+            IteratorMode::From(prefix, Direction::Forward),
+        )
     }
 
     fn raw_iterator<'a: 'b, 'b>(&'a self) -> DBRawIterator<'b> {
-        let opts = ReadOptions::default();
-        self.get_raw_iter(&opts)
+        owned_raw_iter(self, ReadOptions::default())
+    }
+
+    /// Computes a digest of the keys and values in `[from, to)` of the
+    /// default column family, to cheaply compare a range across replicas.
+    ///
+    /// The digest only depends on the data, so it is stable across
+    /// processes, platforms and versions of this crate.
+    fn range_digest<K: AsRef<[u8]>>(&self, from: K, to: K) -> Result<u64, Error>
+    where
+        // keeps `dyn Iterate` usable
+        Self: Sized,
+    {
+        let mut opts = ReadOptions::default();
+        // a scan across prefixes is only complete in total order, and a
+        // full range checksum should not evict the hot blocks from the cache
+        opts.set_total_order_seek(true);
+        opts.fill_cache(false);
+        opts.set_iterate_upper_bound(to);
+        let mut iter = self.get_raw_iter(&opts);
+        digest_from(&mut iter, from.as_ref())
     }
 }
 
 pub trait IterateCF: Iterate {
+    /// Opens a raw iterator over a column family using the provided
+    /// ReadOptions, which must outlive the iterator, see `get_raw_iter`.
     fn get_raw_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBRawIterator<'b>, Error>;
 
     fn get_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
         mode: IteratorMode<'_>,
     ) -> Result<DBIterator<'b>, Error> {
-        let mut rv = DBIterator {
-            raw: self.get_raw_iter_cf(cf_handle, readopts)?,
-            direction: Direction::Forward, // blown away by set_mode()
-            just_seeked: false,
-        };
-        rv.set_mode(mode);
-        Ok(rv)
+        Ok(DBIterator::new(
+            self.get_raw_iter_cf(cf_handle, readopts)?,
+            mode,
+        ))
     }
 
     /// Opens an interator using the provided ReadOptions.
     /// This is used when you want to iterate over a specific ColumnFamily with a modified ReadOptions
+    ///
+    /// RocksDB does not copy the iterate bounds, so the ReadOptions, which
+    /// own the bound buffers, must outlive the iterator.
     fn iterator_cf_opt<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
         mode: IteratorMode<'_>,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBIterator<'b>, Error> {
         self.get_iter_cf(cf_handle, readopts, mode)
     }
@@ -89,8 +114,8 @@ pub trait IterateCF: Iterate {
         cf_handle: &ColumnFamily,
         mode: IteratorMode<'_>,
     ) -> Result<DBIterator<'b>, Error> {
-        let opts = ReadOptions::default();
-        self.get_iter_cf(cf_handle, &opts, mode)
+        let raw = owned_raw_iter_cf(self, cf_handle, ReadOptions::default())?;
+        Ok(DBIterator::new(raw, mode))
     }
 
     fn full_iterator_cf<'a: 'b, 'b>(
@@ -100,7 +125,8 @@ pub trait IterateCF: Iterate {
     ) -> Result<DBIterator<'b>, Error> {
         let mut opts = ReadOptions::default();
         opts.set_total_order_seek(true);
-        self.get_iter_cf(cf_handle, &opts, mode)
+        let raw = owned_raw_iter_cf(self, cf_handle, opts)?;
+        Ok(DBIterator::new(raw, mode))
     }
 
     fn prefix_iterator_cf<'a: 'b, 'b>(
@@ -110,18 +136,91 @@ pub trait IterateCF: Iterate {
     ) -> Result<DBIterator<'b>, Error> {
         let mut opts = ReadOptions::default();
         opts.set_prefix_same_as_start(true);
-        self.get_iter_cf(
-            cf_handle,
-            &opts,
+        let raw = owned_raw_iter_cf(self, cf_handle, opts)?;
+        Ok(DBIterator::new(
+            raw,
             IteratorMode::From(prefix, Direction::Forward),
-        )
+        ))
     }
 
     fn raw_iterator_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
     ) -> Result<DBRawIterator<'b>, Error> {
-        let opts = ReadOptions::default();
-        self.get_raw_iter_cf(cf_handle, &opts)
+        owned_raw_iter_cf(self, cf_handle, ReadOptions::default())
+    }
+
+    /// Computes a digest of the keys and values in `[from, to)` of a column
+    /// family, to cheaply compare a range across replicas.
+    ///
+    /// The digest only depends on the data, so it is stable across
+    /// processes, platforms and versions of this crate.
+    fn range_digest_cf<K: AsRef<[u8]>>(
+        &self,
+        cf_handle: &ColumnFamily,
+        from: K,
+        to: K,
+    ) -> Result<u64, Error> {
+        let mut opts = ReadOptions::default();
+        // a scan across prefixes is only complete in total order, and a
+        // full range checksum should not evict the hot blocks from the cache
+        opts.set_total_order_seek(true);
+        opts.fill_cache(false);
+        opts.set_iterate_upper_bound(to);
+        let mut iter = self.get_raw_iter_cf(cf_handle, &opts)?;
+        digest_from(&mut iter, from.as_ref())
+    }
+}
+
+/// Opens a raw iterator which owns `readopts`, for options created along
+/// with the iterator, which could not outlive it otherwise.
+pub(crate) fn owned_raw_iter<'a: 'b, 'b, T>(db: &'a T, readopts: ReadOptions) -> DBRawIterator<'b>
+where
+    T: Iterate + ?Sized,
+{
+    // moving the options into the iterator leaves the buffers they own,
+    // which RocksDB points to, in place
+    let mut iter = db.get_raw_iter(unsafe { &*(&readopts as *const ReadOptions) });
+    iter.readopts = Some(readopts);
+    iter
+}
+
+/// Same as `owned_raw_iter`, over a column family.
+pub(crate) fn owned_raw_iter_cf<'a: 'b, 'b, T>(
+    db: &'a T,
+    cf_handle: &ColumnFamily,
+    readopts: ReadOptions,
+) -> Result<DBRawIterator<'b>, Error>
+where
+    T: IterateCF + ?Sized,
+{
+    let mut iter = db.get_raw_iter_cf(cf_handle, unsafe { &*(&readopts as *const ReadOptions) })?;
+    iter.readopts = Some(readopts);
+    Ok(iter)
+}
+
+const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0100_0000_01b3;
+
+// 64-bit FNV-1a, each field is prefixed with its length so that moving
+// bytes between a key and its value changes the digest
+fn fnv1a_field(mut hash: u64, field: &[u8]) -> u64 {
+    for byte in (field.len() as u64).to_le_bytes().iter().chain(field) {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(FNV_PRIME);
+    }
+    hash
+}
+
+fn digest_from(iter: &mut DBRawIterator<'_>, from: &[u8]) -> Result<u64, Error> {
+    let mut hash = FNV_OFFSET_BASIS;
+    iter.seek(from);
+    while iter.valid() {
+        // key and value are always present on a valid iterator
+        hash = fnv1a_field(hash, iter.key().unwrap());
+        hash = fnv1a_field(hash, iter.value().unwrap());
+        iter.next();
     }
+    iter.status()?;
+    Ok(hash)
 }
@@ -0,0 +1,99 @@
+use crate::ffi;
+use libc::{c_char, size_t};
+use std::ptr;
+
+use crate::{ColumnFamily, ReadOptions, handle::Handle};
+
+pub trait KeyMayExist {
+    /// Cheaply checks whether a key may be present in the default column
+    /// family, using memtables, bloom filters and cached blocks only.
+    ///
+    /// `false` means the key is definitely absent; `true` means it may be
+    /// present and a `get` is needed to be sure.
+    fn key_may_exist<K: AsRef<[u8]>>(&self, key: K, readopts: Option<&ReadOptions>) -> bool;
+}
+
+pub trait KeyMayExistCF {
+    fn key_may_exist_cf_full<K: AsRef<[u8]>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        key: K,
+        readopts: Option<&ReadOptions>,
+    ) -> bool;
+
+    /// Cheaply checks whether a key may be present in a column family,
+    /// using memtables, bloom filters and cached blocks only.
+    ///
+    /// `false` means the key is definitely absent; `true` means it may be
+    /// present and a `get_cf` is needed to be sure.
+    fn key_may_exist_cf<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        key: K,
+        readopts: Option<&ReadOptions>,
+    ) -> bool {
+        self.key_may_exist_cf_full(Some(cf), key, readopts)
+    }
+}
+
+impl<T> KeyMayExist for T
+where
+    T: KeyMayExistCF,
+{
+    fn key_may_exist<K: AsRef<[u8]>>(&self, key: K, readopts: Option<&ReadOptions>) -> bool {
+        self.key_may_exist_cf_full(None, key, readopts)
+    }
+}
+
+impl<T> KeyMayExistCF for T
+where
+    T: Handle<ffi::rocksdb_t> + super::Read,
+{
+    fn key_may_exist_cf_full<K: AsRef<[u8]>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        key: K,
+        readopts: Option<&ReadOptions>,
+    ) -> bool {
+        let default_readopts;
+        let readopts = match readopts {
+            Some(readopts) => readopts,
+            None => {
+                default_readopts = ReadOptions::default();
+                &default_readopts
+            }
+        };
+
+        let key = key.as_ref();
+
+        // the value is not fetched, so the out parameters are left null
+        unsafe {
+            let may_exist = match cf {
+                Some(cf) => ffi::rocksdb_key_may_exist_cf(
+                    self.handle(),
+                    readopts.handle(),
+                    cf.inner,
+                    key.as_ptr() as *const c_char,
+                    key.len() as size_t,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                    ptr::null(),
+                    0,
+                    ptr::null_mut(),
+                ),
+                None => ffi::rocksdb_key_may_exist(
+                    self.handle(),
+                    readopts.handle(),
+                    key.as_ptr() as *const c_char,
+                    key.len() as size_t,
+                    ptr::null_mut(),
+                    ptr::null_mut(),
+                    ptr::null(),
+                    0,
+                    ptr::null_mut(),
+                ),
+            };
+            may_exist != 0
+        }
+    }
+}
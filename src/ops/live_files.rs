@@ -0,0 +1,130 @@
+use crate::{ColumnFamily, Error, handle::Handle};
+use libc::{c_void, size_t};
+use std::ffi::CStr;
+use std::slice;
+
+/// The metadata that describes a SST file
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiveFile {
+    /// Name of the column family the file belongs to
+    pub column_family_name: String,
+    /// Name of the file
+    pub name: String,
+    /// Size of the file
+    pub size: usize,
+    /// Level at which this file resides
+    pub level: i32,
+    /// Smallest user defined key in the file
+    pub start_key: Option<Vec<u8>>,
+    /// Largest user defined key in the file
+    pub end_key: Option<Vec<u8>>,
+    /// Number of entries/alive keys in the file
+    pub num_entries: u64,
+    /// Number of deletions/tomb key(s) in the file
+    pub num_deletions: u64,
+}
+
+pub trait LiveFiles {
+    /// Returns a list of all table files with their level, start key
+    /// and end key
+    fn live_files(&self) -> Result<Vec<LiveFile>, Error>;
+
+    /// Returns the table files of the default column family residing at
+    /// the given LSM level
+    fn live_files_at_level(&self, level: i32) -> Result<Vec<LiveFile>, Error> {
+        Ok(self
+            .live_files()?
+            .into_iter()
+            .filter(|f| f.column_family_name == "default" && f.level == level)
+            .collect())
+    }
+
+    /// Returns the table files of a column family residing at the given
+    /// LSM level
+    fn live_files_at_level_cf(
+        &self,
+        cf: &ColumnFamily,
+        level: i32,
+    ) -> Result<Vec<LiveFile>, Error> {
+        let name = column_family_name(cf);
+        Ok(self
+            .live_files()?
+            .into_iter()
+            .filter(|f| f.column_family_name == name && f.level == level)
+            .collect())
+    }
+}
+
+impl<T> LiveFiles for T
+where
+    T: Handle<ffi::rocksdb_t> + super::Read,
+{
+    fn live_files(&self) -> Result<Vec<LiveFile>, Error> {
+        unsafe {
+            let files = ffi::rocksdb_livefiles(self.handle());
+            if files.is_null() {
+                return Err(Error::new("Could not get live files".to_owned()));
+            }
+
+            let n = ffi::rocksdb_livefiles_count(files);
+
+            let mut livefiles = Vec::with_capacity(n as usize);
+            let mut key_size: size_t = 0;
+
+            for i in 0..n {
+                let column_family_name =
+                    CStr::from_ptr(ffi::rocksdb_livefiles_column_family_name(files, i))
+                        .to_string_lossy()
+                        .into_owned();
+                let name = CStr::from_ptr(ffi::rocksdb_livefiles_name(files, i))
+                    .to_string_lossy()
+                    .into_owned();
+                let size = ffi::rocksdb_livefiles_size(files, i);
+                let level = ffi::rocksdb_livefiles_level(files, i);
+
+                // get smallest key inside file
+                let smallest_key = ffi::rocksdb_livefiles_smallestkey(files, i, &mut key_size);
+                let start_key = raw_data(smallest_key, key_size);
+
+                // get largest key inside file
+                let largest_key = ffi::rocksdb_livefiles_largestkey(files, i, &mut key_size);
+                let end_key = raw_data(largest_key, key_size);
+
+                livefiles.push(LiveFile {
+                    column_family_name,
+                    name,
+                    size,
+                    level,
+                    start_key,
+                    end_key,
+                    num_entries: ffi::rocksdb_livefiles_entries(files, i),
+                    num_deletions: ffi::rocksdb_livefiles_deletions(files, i),
+                });
+            }
+
+            // destroy livefiles metadata(s)
+            ffi::rocksdb_livefiles_destroy(files);
+
+            Ok(livefiles)
+        }
+    }
+}
+
+fn column_family_name(cf: &ColumnFamily) -> String {
+    unsafe {
+        let mut name_len: size_t = 0;
+        let name = ffi::rocksdb_column_family_handle_get_name(cf.inner, &mut name_len);
+        let result = String::from_utf8_lossy(slice::from_raw_parts(name as *const u8, name_len))
+            .into_owned();
+        ffi::rocksdb_free(name as *mut c_void);
+        result
+    }
+}
+
+unsafe fn raw_data(ptr: *const libc::c_char, size: size_t) -> Option<Vec<u8>> {
+    if ptr.is_null() {
+        None
+    } else {
+        unsafe { Some(slice::from_raw_parts(ptr as *const u8, size).to_vec()) }
+    }
+}
@@ -16,7 +16,9 @@
 // PIGMED operations (Put, Iterate, Get, Merge, Delete)
 mod columnfamily;
 mod delete;
+mod delete_file_in_range;
 mod get;
+mod get_merge_operands;
 mod get_pinned;
 mod merge;
 mod put;
@@ -24,24 +26,36 @@ mod writebatch;
 
 mod open;
 
+mod approximate_sizes;
 mod checkpoint;
 mod compact;
+mod compact_files;
 mod flush;
 mod ingest_external_file;
 mod iter;
+mod key_may_exist;
+mod live_files;
 mod multi_get;
 mod property;
 mod setoptions;
 mod transaction;
 
 pub use self::delete::{Delete, DeleteCF};
-pub use self::get::{Get, GetCF};
+pub use self::delete_file_in_range::{DeleteFileInRange, DeleteFileInRangeCF};
+pub use self::get::{Get, GetCF, GetWithTs, GetWithTsCF};
+pub use self::get_merge_operands::{GetMergeOperands, GetMergeOperandsCF};
+pub(crate) use self::get_pinned::get_pinned_cf_raw;
 pub use self::get_pinned::{GetPinned, GetPinnedCF};
-pub use self::ingest_external_file::{IngestExternalFile, IngestExternalFileCF};
+pub use self::ingest_external_file::{
+    IngestExternalFile, IngestExternalFileCF, IngestSstBytes, IngestSstBytesCF,
+};
+pub(crate) use self::iter::{owned_raw_iter, owned_raw_iter_cf};
 pub use self::merge::{Merge, MergeCF};
-pub use self::multi_get::{BatchedMultiGetCF, CFAndKey, MultiGet, MultiGetCF, convert_values};
-pub use self::put::{Put, PutCF};
-pub use self::writebatch::WriteOps;
+pub use self::multi_get::{
+    BatchedMultiGet, BatchedMultiGetCF, CFAndKey, MultiGet, MultiGetCF, convert_values,
+};
+pub use self::put::{Put, PutCF, PutWithTs, PutWithTsCF};
+pub use self::writebatch::{WriteBatchWithIndexOps, WriteOps};
 
 pub use self::open::{Open, OpenCF};
 
@@ -53,13 +67,17 @@ pub trait Read {}
 /// DB state
 pub trait Write {}
 
+pub use self::approximate_sizes::{GetApproximateSizes, GetApproximateSizesCF};
 pub use self::checkpoint::CreateCheckpointObject;
 pub use self::columnfamily::CreateCF;
 pub use self::columnfamily::DropCF;
 pub use self::columnfamily::GetColumnFamilys;
 pub use self::compact::{CompactRange, CompactRangeCF};
-pub use self::flush::Flush;
+pub use self::compact_files::{CompactFiles, CompactFilesCF};
+pub use self::flush::{Flush, FlushCF, FlushRangeCF};
 pub use self::iter::{Iterate, IterateCF};
+pub use self::key_may_exist::{KeyMayExist, KeyMayExistCF};
+pub use self::live_files::{LiveFile, LiveFiles};
 pub use self::property::{GetProperty, GetPropertyCF};
 pub use self::setoptions::SetOptions;
 pub use self::transaction::TransactionBegin;
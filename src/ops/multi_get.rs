@@ -16,6 +16,7 @@
 use crate::{ColumnFamily, DBPinnableSlice, DBVector, ffi};
 use libc::c_char;
 use std::ptr;
+use std::thread;
 
 use crate::{Error, ReadOptions, handle::Handle};
 
@@ -46,6 +47,40 @@ pub trait MultiGet<R> {
     {
         self.multi_get_full(keys, Some(readopts))
     }
+
+    /// Return the values associated with the given keys, splitting them in
+    /// up to `num_threads` chunks which are looked up concurrently. The
+    /// results are in the order of `keys`, as with `multi_get`.
+    ///
+    /// This only pays off for large key sets whose lookups hit the disk.
+    fn multi_get_parallel<K>(
+        &self,
+        keys: &[K],
+        num_threads: usize,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        Self: Sync,
+        K: AsRef<[u8]> + Sync,
+    {
+        if keys.is_empty() {
+            return Vec::new();
+        }
+        let chunk_size = keys.len().div_ceil(num_threads.max(1));
+        thread::scope(|scope| {
+            let handles: Vec<_> = keys
+                .chunks(chunk_size)
+                .map(|chunk| scope.spawn(move || self.multi_get(chunk)))
+                .collect();
+            handles
+                .into_iter()
+                .flat_map(|handle| {
+                    handle
+                        .join()
+                        .unwrap_or_else(|e| std::panic::resume_unwind(e))
+                })
+                .collect()
+        })
+    }
 }
 
 pub trait MultiGetCF<R> {
@@ -77,6 +112,31 @@ pub trait MultiGetCF<R> {
     {
         self.multi_get_cf_full(keys_cf, Some(readopts))
     }
+
+    /// Return the values associated with the given keys and column families,
+    /// splitting them in one chunk per thread of the rayon pool. The results
+    /// are in the order of `keys_cf`, as with `multi_get_cf`.
+    #[cfg(feature = "rayon")]
+    fn par_multi_get_cf<K>(
+        &self,
+        keys_cf: &[(&ColumnFamily, K)],
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        Self: Sync,
+        K: AsRef<[u8]> + Sync,
+    {
+        use rayon::prelude::*;
+
+        if keys_cf.is_empty() {
+            return Vec::new();
+        }
+        let chunk_size = keys_cf.len().div_ceil(rayon::current_num_threads());
+        let chunks: Vec<_> = keys_cf
+            .par_chunks(chunk_size)
+            .map(|chunk| self.multi_get_cf(chunk.iter().map(|(cf, key)| (*cf, key))))
+            .collect();
+        chunks.into_iter().flatten().collect()
+    }
 }
 
 impl<T> MultiGet<ReadOptions> for T
@@ -110,6 +170,9 @@ where
             .into_iter()
             .map(|k| (Box::from(k.as_ref()), k.as_ref().len()))
             .unzip();
+        if keys.is_empty() {
+            return Vec::new();
+        }
         let ptr_keys: Vec<_> = keys.iter().map(|k| k.as_ptr() as *const c_char).collect();
         let mut values = vec![ptr::null_mut(); keys.len()];
         let mut values_sizes = vec![0_usize; keys.len()];
@@ -160,6 +223,9 @@ where
             .into_iter()
             .map(|(cf, key)| ((cf, Box::from(key.as_ref())), key.as_ref().len()))
             .unzip();
+        if cfs_and_keys.is_empty() {
+            return Vec::new();
+        }
         let ptr_keys: Vec<_> = cfs_and_keys
             .iter()
             .map(|(_, k)| k.as_ptr() as *const c_char)
@@ -265,6 +331,9 @@ where
                 (k.as_ptr() as *const c_char, k.len())
             })
             .unzip();
+        if ptr_keys.is_empty() {
+            return Vec::new();
+        }
 
         let mut pinned_values = vec![ptr::null_mut(); ptr_keys.len()];
         let mut errors = vec![ptr::null_mut(); ptr_keys.len()];
@@ -300,6 +369,70 @@ where
     }
 }
 
+pub trait BatchedMultiGet<R> {
+    fn batched_multi_get_full<'a, K, I>(
+        &'a self,
+        keys: I,
+        sorted_input: bool,
+        readopts: Option<&R>,
+    ) -> Vec<Result<Option<DBPinnableSlice<'a>>, Error>>
+    where
+        K: AsRef<[u8]> + 'a + ?Sized,
+        I: IntoIterator<Item = &'a K>;
+
+    /// Return the values associated with the given keys in the default column family,
+    /// pinned in place instead of copied out of RocksDB.
+    fn batched_multi_get<'a, K, I>(
+        &'a self,
+        keys: I,
+        sorted_input: bool,
+    ) -> Vec<Result<Option<DBPinnableSlice<'a>>, Error>>
+    where
+        K: AsRef<[u8]> + 'a + ?Sized,
+        I: IntoIterator<Item = &'a K>,
+    {
+        self.batched_multi_get_full(keys, sorted_input, None)
+    }
+
+    fn batched_multi_get_opt<'a, K, I>(
+        &'a self,
+        keys: I,
+        sorted_input: bool,
+        readopts: &R,
+    ) -> Vec<Result<Option<DBPinnableSlice<'a>>, Error>>
+    where
+        K: AsRef<[u8]> + 'a + ?Sized,
+        I: IntoIterator<Item = &'a K>,
+    {
+        self.batched_multi_get_full(keys, sorted_input, Some(readopts))
+    }
+}
+
+impl<T> BatchedMultiGet<ReadOptions> for T
+where
+    T: Handle<ffi::rocksdb_t> + super::Read,
+{
+    fn batched_multi_get_full<'a, K, I>(
+        &'a self,
+        keys: I,
+        sorted_input: bool,
+        readopts: Option<&ReadOptions>,
+    ) -> Vec<Result<Option<DBPinnableSlice<'a>>, Error>>
+    where
+        K: AsRef<[u8]> + 'a + ?Sized,
+        I: IntoIterator<Item = &'a K>,
+    {
+        unsafe {
+            // the default column family handle is immortal, destroying it only frees the wrapper
+            let cf =
+                ColumnFamily::new(ffi::rocksdb_get_default_column_family_handle(self.handle()));
+            let values = self.batched_multi_get_cf_full(&cf, keys, sorted_input, readopts);
+            ffi::rocksdb_column_family_handle_destroy(cf.inner);
+            values
+        }
+    }
+}
+
 pub fn convert_values(
     values: Vec<*mut c_char>,
     values_sizes: Vec<usize>,
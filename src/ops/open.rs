@@ -67,6 +67,9 @@ pub trait OpenCF: OpenRaw {
     }
 
     /// Open a database with the given database options and column family descriptors.
+    ///
+    /// Each column family is opened with the `Options` of its descriptor and `opts` only applies
+    /// to the database itself. A `default` column family that is not listed gets `Options::default()`.
     fn open_cf_descriptors<P, I>(opts: &Options, path: P, cfs: I) -> Result<Self, Error>
     where
         P: AsRef<Path>,
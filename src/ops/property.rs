@@ -1,7 +1,54 @@
 use crate::{ColumnFamily, Error, handle::Handle};
 use libc::{c_char, c_void};
+use std::collections::BTreeMap;
 use std::ffi::{CStr, CString};
 
+/// The properties queried by `GetProperty::property_all`.
+const KNOWN_PROPERTIES: &[&str] = &[
+    "rocksdb.stats",
+    "rocksdb.dbstats",
+    "rocksdb.cfstats",
+    "rocksdb.cfstats-no-file-histogram",
+    "rocksdb.cf-file-histogram",
+    "rocksdb.levelstats",
+    "rocksdb.sstables",
+    "rocksdb.options-statistics",
+    "rocksdb.num-immutable-mem-table",
+    "rocksdb.num-immutable-mem-table-flushed",
+    "rocksdb.mem-table-flush-pending",
+    "rocksdb.num-running-flushes",
+    "rocksdb.compaction-pending",
+    "rocksdb.num-running-compactions",
+    "rocksdb.background-errors",
+    "rocksdb.cur-size-active-mem-table",
+    "rocksdb.cur-size-all-mem-tables",
+    "rocksdb.size-all-mem-tables",
+    "rocksdb.num-entries-active-mem-table",
+    "rocksdb.num-entries-imm-mem-tables",
+    "rocksdb.num-deletes-active-mem-table",
+    "rocksdb.num-deletes-imm-mem-tables",
+    "rocksdb.estimate-num-keys",
+    "rocksdb.estimate-table-readers-mem",
+    "rocksdb.is-file-deletions-enabled",
+    "rocksdb.num-snapshots",
+    "rocksdb.oldest-snapshot-time",
+    "rocksdb.num-live-versions",
+    "rocksdb.current-super-version-number",
+    "rocksdb.estimate-live-data-size",
+    "rocksdb.min-log-number-to-keep",
+    "rocksdb.min-obsolete-sst-number-to-keep",
+    "rocksdb.total-sst-files-size",
+    "rocksdb.live-sst-files-size",
+    "rocksdb.base-level",
+    "rocksdb.estimate-pending-compaction-bytes",
+    "rocksdb.actual-delayed-write-rate",
+    "rocksdb.is-write-stopped",
+    "rocksdb.estimate-oldest-key-time",
+    "rocksdb.block-cache-capacity",
+    "rocksdb.block-cache-usage",
+    "rocksdb.block-cache-pinned-usage",
+];
+
 pub trait GetProperty {
     /// Retrieves a RocksDB property by name.
     ///
@@ -14,6 +61,77 @@ pub trait GetProperty {
     /// For a full list of properties that return int values, see
     /// https://github.com/facebook/rocksdb/blob/08809f5e6cd9cc4bc3958dd4d59457ae78c76660/include/rocksdb/db.h#L654-L689
     fn property_int_value(&self, name: &str) -> Result<Option<u64>, Error>;
+
+    /// Returns the number of currently running compactions
+    /// (`rocksdb.num-running-compactions`).
+    fn num_running_compactions(&self) -> Result<Option<u64>, Error> {
+        self.property_int_value("rocksdb.num-running-compactions")
+    }
+
+    /// Returns the number of currently running flushes
+    /// (`rocksdb.num-running-flushes`).
+    fn num_running_flushes(&self) -> Result<Option<u64>, Error> {
+        self.property_int_value("rocksdb.num-running-flushes")
+    }
+
+    /// Returns true if a memtable flush of the default column family is
+    /// pending (`rocksdb.mem-table-flush-pending`).
+    fn mem_table_flush_pending(&self) -> Result<bool, Error> {
+        self.property_int_value("rocksdb.mem-table-flush-pending")
+            .map(|v| v.unwrap_or_default() != 0)
+    }
+
+    /// Returns the number of unreleased snapshots (`rocksdb.num-snapshots`).
+    /// Long-held snapshots keep compaction from dropping obsolete versions.
+    fn num_snapshots(&self) -> Result<Option<u64>, Error> {
+        self.property_int_value("rocksdb.num-snapshots")
+    }
+
+    /// Returns the unix time in seconds of the oldest unreleased snapshot,
+    /// which is 0 if there is none (`rocksdb.oldest-snapshot-time`).
+    fn oldest_snapshot_time(&self) -> Result<Option<u64>, Error> {
+        self.property_int_value("rocksdb.oldest-snapshot-time")
+    }
+
+    /// Returns the estimated number of keys in the default column family
+    /// (`rocksdb.estimate-num-keys`).
+    fn estimate_num_keys(&self) -> Result<Option<u64>, Error> {
+        self.property_int_value("rocksdb.estimate-num-keys")
+    }
+
+    /// Returns the estimated size in bytes of the live data in the default
+    /// column family (`rocksdb.estimate-live-data-size`).
+    fn estimate_live_data_size(&self) -> Result<Option<u64>, Error> {
+        self.property_int_value("rocksdb.estimate-live-data-size")
+    }
+
+    /// Returns the total size in bytes of all the SST files of the default
+    /// column family, including obsolete ones (`rocksdb.total-sst-files-size`).
+    fn total_sst_files_size(&self) -> Result<Option<u64>, Error> {
+        self.property_int_value("rocksdb.total-sst-files-size")
+    }
+
+    /// Returns the approximate size in bytes of the active and unflushed
+    /// immutable memtables of the default column family
+    /// (`rocksdb.cur-size-all-mem-tables`).
+    fn cur_size_all_mem_tables(&self) -> Result<Option<u64>, Error> {
+        self.property_int_value("rocksdb.cur-size-all-mem-tables")
+    }
+
+    /// Retrieves all the commonly used properties of the default column
+    /// family, from `rocksdb.stats` to the memtable, compaction and block
+    /// cache gauges, e.g. to export them for monitoring. Properties which are
+    /// not available, like the block cache ones without a block cache, are
+    /// left out.
+    fn property_all(&self) -> Result<BTreeMap<String, String>, Error> {
+        let mut properties = BTreeMap::new();
+        for name in KNOWN_PROPERTIES {
+            if let Some(value) = self.property_value(name)? {
+                properties.insert((*name).to_owned(), value);
+            }
+        }
+        Ok(properties)
+    }
 }
 
 pub trait GetPropertyCF {
@@ -28,6 +146,38 @@ pub trait GetPropertyCF {
     /// For a full list of properties that return int values, see
     /// https://github.com/facebook/rocksdb/blob/08809f5e6cd9cc4bc3958dd4d59457ae78c76660/include/rocksdb/db.h#L654-L689
     fn property_int_value_cf(&self, cf: &ColumnFamily, name: &str) -> Result<Option<u64>, Error>;
+
+    /// Returns true if a memtable flush of a specific column family is
+    /// pending (`rocksdb.mem-table-flush-pending`).
+    fn mem_table_flush_pending_cf(&self, cf: &ColumnFamily) -> Result<bool, Error> {
+        self.property_int_value_cf(cf, "rocksdb.mem-table-flush-pending")
+            .map(|v| v.unwrap_or_default() != 0)
+    }
+
+    /// Returns the estimated number of keys in a specific column family
+    /// (`rocksdb.estimate-num-keys`).
+    fn estimate_num_keys_cf(&self, cf: &ColumnFamily) -> Result<Option<u64>, Error> {
+        self.property_int_value_cf(cf, "rocksdb.estimate-num-keys")
+    }
+
+    /// Returns the estimated size in bytes of the live data in a specific
+    /// column family (`rocksdb.estimate-live-data-size`).
+    fn estimate_live_data_size_cf(&self, cf: &ColumnFamily) -> Result<Option<u64>, Error> {
+        self.property_int_value_cf(cf, "rocksdb.estimate-live-data-size")
+    }
+
+    /// Returns the total size in bytes of all the SST files of a specific
+    /// column family, including obsolete ones (`rocksdb.total-sst-files-size`).
+    fn total_sst_files_size_cf(&self, cf: &ColumnFamily) -> Result<Option<u64>, Error> {
+        self.property_int_value_cf(cf, "rocksdb.total-sst-files-size")
+    }
+
+    /// Returns the approximate size in bytes of the active and unflushed
+    /// immutable memtables of a specific column family
+    /// (`rocksdb.cur-size-all-mem-tables`).
+    fn cur_size_all_mem_tables_cf(&self, cf: &ColumnFamily) -> Result<Option<u64>, Error> {
+        self.property_int_value_cf(cf, "rocksdb.cur-size-all-mem-tables")
+    }
 }
 
 impl<T> GetProperty for T
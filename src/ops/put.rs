@@ -142,3 +142,122 @@ where
         }
     }
 }
+
+pub trait PutWithTs {
+    /// Insert a value into the database under the given key at timestamp
+    /// `ts`. The default column family has to use a comparator with
+    /// timestamps, see `Options::set_comparator_with_ts`.
+    fn put_with_ts<K, S, V>(&self, key: K, ts: S, value: V) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        S: AsRef<[u8]>,
+        V: AsRef<[u8]>;
+}
+
+pub trait PutWithTsCF {
+    fn put_cf_with_ts_full<K, S, V>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        key: K,
+        ts: S,
+        value: V,
+        writeopts: Option<&WriteOptions>,
+    ) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        S: AsRef<[u8]>,
+        V: AsRef<[u8]>;
+
+    /// Insert a value into a column family under the given key at timestamp
+    /// `ts`. The column family has to use a comparator with timestamps, see
+    /// `Options::set_comparator_with_ts`.
+    fn put_cf_with_ts<K, S, V>(
+        &self,
+        cf: &ColumnFamily,
+        key: K,
+        ts: S,
+        value: V,
+    ) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        S: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.put_cf_with_ts_full(Some(cf), key, ts, value, None)
+    }
+}
+
+impl<T> PutWithTs for T
+where
+    T: PutWithTsCF,
+{
+    fn put_with_ts<K, S, V>(&self, key: K, ts: S, value: V) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        S: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.put_cf_with_ts_full(None, key, ts, value, None)
+    }
+}
+
+impl<T> PutWithTsCF for T
+where
+    T: Handle<ffi::rocksdb_t> + super::Write,
+{
+    fn put_cf_with_ts_full<K, S, V>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        key: K,
+        ts: S,
+        value: V,
+        writeopts: Option<&WriteOptions>,
+    ) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        S: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let mut default_writeopts = None;
+
+        let wo_handle = WriteOptions::input_or_default(writeopts, &mut default_writeopts)?;
+
+        let key = key.as_ref();
+        let ts = ts.as_ref();
+        let value = value.as_ref();
+        let key_ptr = key.as_ptr() as *const c_char;
+        let key_len = key.len() as size_t;
+        let ts_ptr = ts.as_ptr() as *const c_char;
+        let ts_len = ts.len() as size_t;
+        let val_ptr = value.as_ptr() as *const c_char;
+        let val_len = value.len() as size_t;
+
+        unsafe {
+            match cf {
+                Some(cf) => ffi_try!(ffi::rocksdb_put_cf_with_ts(
+                    self.handle(),
+                    wo_handle,
+                    cf.handle(),
+                    key_ptr,
+                    key_len,
+                    ts_ptr,
+                    ts_len,
+                    val_ptr,
+                    val_len,
+                )),
+                None => ffi_try!(ffi::rocksdb_put_with_ts(
+                    self.handle(),
+                    wo_handle,
+                    key_ptr,
+                    key_len,
+                    ts_ptr,
+                    ts_len,
+                    val_ptr,
+                    val_len,
+                )),
+            }
+
+            Ok(())
+        }
+    }
+}
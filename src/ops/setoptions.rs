@@ -5,6 +5,24 @@ use std::ffi::CString;
 pub trait SetOptions {
     fn set_options(&self, opts: &[(&str, &str)]) -> Result<(), Error>;
     fn set_options_cf(&self, cf: &ColumnFamily, opts: &[(&str, &str)]) -> Result<(), Error>;
+
+    /// Dynamically changes DB-wide options, such as `max_background_jobs`,
+    /// as opposed to `set_options` which changes the options of the default
+    /// column family.
+    fn set_db_options(&self, opts: &[(&str, &str)]) -> Result<(), Error>;
+
+    /// Stops scheduling automatic compactions for a column family, e.g.
+    /// while bulk loading it. Compactions already running are not aborted,
+    /// and manual compactions are still allowed.
+    fn pause_compactions_cf(&self, cf: &ColumnFamily) -> Result<(), Error> {
+        self.set_options_cf(cf, &[("disable_auto_compactions", "true")])
+    }
+
+    /// Re-enables automatic compactions for a column family paused by
+    /// `pause_compactions_cf`.
+    fn resume_compactions_cf(&self, cf: &ColumnFamily) -> Result<(), Error> {
+        self.set_options_cf(cf, &[("disable_auto_compactions", "false")])
+    }
 }
 
 impl<T> SetOptions for T
@@ -45,6 +63,23 @@ where
         }
         Ok(())
     }
+
+    fn set_db_options(&self, opts: &[(&str, &str)]) -> Result<(), Error> {
+        let copts = build_coptions(opts)?;
+        let cnames: Vec<*const c_char> = copts.iter().map(|opt| opt.0.as_ptr()).collect();
+        let cvalues: Vec<*const c_char> = copts.iter().map(|opt| opt.1.as_ptr()).collect();
+        let count = opts.len() as i32;
+
+        unsafe {
+            ffi_try!(ffi::rocksdb_set_db_options(
+                self.handle(),
+                count,
+                cnames.as_ptr(),
+                cvalues.as_ptr(),
+            ));
+        }
+        Ok(())
+    }
 }
 
 fn build_coptions(opts: &[(&str, &str)]) -> Result<Vec<(CString, CString)>, Error> {
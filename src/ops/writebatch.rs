@@ -1,6 +1,6 @@
 use crate::ffi;
 
-use crate::{Error, WriteBatch, WriteOptions, handle::Handle};
+use crate::{Error, WriteBatch, WriteBatchWithIndex, WriteOptions, handle::Handle};
 
 pub trait WriteOps {
     fn write_full(&self, batch: &WriteBatch, writeopts: Option<&WriteOptions>)
@@ -40,3 +40,47 @@ where
         }
     }
 }
+
+pub trait WriteBatchWithIndexOps {
+    fn write_wbwi_full(
+        &self,
+        batch: &WriteBatchWithIndex,
+        writeopts: Option<&WriteOptions>,
+    ) -> Result<(), Error>;
+
+    fn write_wbwi(&self, batch: &WriteBatchWithIndex) -> Result<(), Error> {
+        self.write_wbwi_full(batch, None)
+    }
+
+    fn write_wbwi_opt(
+        &self,
+        batch: &WriteBatchWithIndex,
+        writeopts: &WriteOptions,
+    ) -> Result<(), Error> {
+        self.write_wbwi_full(batch, Some(writeopts))
+    }
+}
+
+impl<T> WriteBatchWithIndexOps for T
+where
+    T: Handle<ffi::rocksdb_t> + super::Write,
+{
+    fn write_wbwi_full(
+        &self,
+        batch: &WriteBatchWithIndex,
+        writeopts: Option<&WriteOptions>,
+    ) -> Result<(), Error> {
+        let mut default_writeopts = None;
+
+        let wo_handle = WriteOptions::input_or_default(writeopts, &mut default_writeopts)?;
+
+        unsafe {
+            ffi_try!(ffi::rocksdb_write_writebatch_wi(
+                self.handle(),
+                wo_handle,
+                batch.handle(),
+            ));
+            Ok(())
+        }
+    }
+}
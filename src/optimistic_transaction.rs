@@ -2,47 +2,150 @@ use crate::ffi;
 use crate::{
     ColumnFamily, DBPinnableSlice, DBRawIterator, DBVector, Error, ReadOptions, ffi_util,
     handle::{ConstHandle, Handle},
-    ops::*,
+    ops::{get_pinned_cf_raw, *},
 };
 use libc::{c_char, c_uchar, c_void, size_t};
+use std::collections::HashMap;
 use std::marker::PhantomData;
 use std::ptr;
+use std::sync::Mutex;
 
 pub struct OptimisticTransaction {
     inner: *mut ffi::rocksdb_transaction_t,
+    base_db: *mut ffi::rocksdb_t,
+    // Keys written or read for update by this transaction. Used to find out
+    // which keys conflicted when commit fails.
+    tracked_keys: Mutex<TrackedKeys>,
+}
+
+#[derive(Default)]
+struct TrackedKeys {
+    // per column family, each key along with the number of keys tracked
+    // before it and the sequence number it conflicts after
+    keys: HashMap<*mut ffi::rocksdb_column_family_handle_t, HashMap<Vec<u8>, (usize, u64)>>,
+    len: usize,
+    // the number of keys tracked when each savepoint was set
+    savepoints: Vec<usize>,
+}
+
+impl TrackedKeys {
+    fn insert(&mut self, cf: *mut ffi::rocksdb_column_family_handle_t, key: &[u8], seq: u64) {
+        let keys = self.keys.entry(cf).or_default();
+        if !keys.contains_key(key) {
+            keys.insert(key.to_vec(), (self.len, seq));
+            self.len += 1;
+        }
+    }
+
+    fn set_savepoint(&mut self) {
+        self.savepoints.push(self.len);
+    }
+
+    fn rollback_to_savepoint(&mut self) {
+        if let Some(len) = self.savepoints.pop() {
+            for keys in self.keys.values_mut() {
+                keys.retain(|_, (pos, _)| *pos < len);
+            }
+            self.len = len;
+        }
+    }
 }
 
 unsafe impl Send for OptimisticTransaction {}
 unsafe impl Sync for OptimisticTransaction {}
 
 impl OptimisticTransaction {
-    pub(crate) fn new(inner: *mut ffi::rocksdb_transaction_t) -> OptimisticTransaction {
-        OptimisticTransaction { inner }
+    pub(crate) fn new(
+        inner: *mut ffi::rocksdb_transaction_t,
+        base_db: *mut ffi::rocksdb_t,
+    ) -> OptimisticTransaction {
+        OptimisticTransaction {
+            inner,
+            base_db,
+            tracked_keys: Mutex::default(),
+        }
     }
 
     /// commits a transaction
+    ///
+    /// When the commit fails because of a write conflict, the conflicting
+    /// keys are available via [`Error::conflict_keys`].
     pub fn commit(&self) -> Result<(), Error> {
         unsafe {
-            ffi_try!(ffi::rocksdb_transaction_commit(self.inner,));
+            let mut err: *mut c_char = ptr::null_mut();
+            ffi::rocksdb_transaction_commit(self.inner, &mut err);
+            if !err.is_null() {
+                let error = Error::new(ffi_util::error_message(err));
+                return Err(error.with_conflict_keys(self.conflict_keys()));
+            }
         }
         Ok(())
     }
 
+    fn track_key(&self, cf: Option<&ColumnFamily>, key: &[u8]) {
+        let cf = cf.map_or(ptr::null_mut(), |cf| cf.inner);
+        // like RocksDB, a key conflicts when it changed after the transaction
+        // snapshot, or without one after the key was first tracked
+        let seq = self
+            .snapshot()
+            .sequence_number()
+            .unwrap_or_else(|| unsafe { ffi::rocksdb_get_latest_sequence_number(self.base_db) });
+        self.tracked_keys.lock().unwrap().insert(cf, key, seq);
+    }
+
+    fn conflict_keys(&self) -> Vec<Vec<u8>> {
+        let tracked_keys = self.tracked_keys.lock().unwrap();
+        let mut conflict_keys: Vec<_> = tracked_keys
+            .keys
+            .iter()
+            .flat_map(|(cf, keys)| {
+                keys.iter()
+                    .map(move |(key, (pos, seq))| (*cf, key, *pos, *seq))
+            })
+            .filter(|(cf, key, _, seq)| unsafe {
+                let mut err: *mut c_char = ptr::null_mut();
+                let changed = ffi::rocksdb_key_changed_since(
+                    self.base_db,
+                    *cf,
+                    key.as_ptr() as *const c_char,
+                    key.len() as size_t,
+                    *seq,
+                    &mut err,
+                );
+                // a key which can't be checked may have conflicted as well
+                if !err.is_null() {
+                    ffi::rocksdb_free(err as *mut c_void);
+                    return true;
+                }
+                changed != 0
+            })
+            .collect();
+        // report the keys in the order the transaction first touched them
+        conflict_keys.sort_by_key(|(_, _, pos, _)| *pos);
+        conflict_keys
+            .into_iter()
+            .map(|(_, key, _, _)| key.clone())
+            .collect()
+    }
+
     /// Transaction rollback
     pub fn rollback(&self) -> Result<(), Error> {
         unsafe { ffi_try!(ffi::rocksdb_transaction_rollback(self.inner,)) }
+        *self.tracked_keys.lock().unwrap() = TrackedKeys::default();
         Ok(())
     }
 
     /// Transaction rollback to savepoint
     pub fn rollback_to_savepoint(&self) -> Result<(), Error> {
         unsafe { ffi_try!(ffi::rocksdb_transaction_rollback_to_savepoint(self.inner,)) }
+        self.tracked_keys.lock().unwrap().rollback_to_savepoint();
         Ok(())
     }
 
     /// Set savepoint for transaction
     pub fn set_savepoint(&self) {
         unsafe { ffi::rocksdb_transaction_set_savepoint(self.inner) }
+        self.tracked_keys.lock().unwrap().set_savepoint();
     }
 
     /// Get Snapshot
@@ -74,6 +177,7 @@ impl OptimisticTransaction {
         let key = key.as_ref();
         let key_ptr = key.as_ptr() as *const c_char;
         let key_len = key.len() as size_t;
+        self.track_key(None, key);
         unsafe {
             let mut val_len: size_t = 0;
             let val = ffi_try!(ffi::rocksdb_transaction_get_for_update(
@@ -112,6 +216,7 @@ impl OptimisticTransaction {
         let key = key.as_ref();
         let key_ptr = key.as_ptr() as *const c_char;
         let key_len = key.len() as size_t;
+        self.track_key(Some(cf), key);
         unsafe {
             let mut val_len: size_t = 0;
             let val = ffi_try!(ffi::rocksdb_transaction_get_for_update_cf(
@@ -221,6 +326,9 @@ impl MultiGet<ReadOptions> for OptimisticTransaction {
             .into_iter()
             .map(|k| (Box::from(k.as_ref()), k.as_ref().len()))
             .unzip();
+        if keys.is_empty() {
+            return Vec::new();
+        }
         let ptr_keys: Vec<_> = keys.iter().map(|k| k.as_ptr() as *const c_char).collect();
 
         let mut values = vec![ptr::null_mut(); keys.len()];
@@ -270,6 +378,9 @@ impl MultiGetCF<ReadOptions> for OptimisticTransaction {
             .into_iter()
             .map(|(cf, key)| ((cf, Box::from(key.as_ref())), key.as_ref().len()))
             .unzip();
+        if cfs_and_keys.is_empty() {
+            return Vec::new();
+        }
         let ptr_keys: Vec<_> = cfs_and_keys
             .iter()
             .map(|(_, k)| k.as_ptr() as *const c_char)
@@ -301,11 +412,12 @@ impl MultiGetCF<ReadOptions> for OptimisticTransaction {
 }
 
 impl Iterate for OptimisticTransaction {
-    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &'b ReadOptions) -> DBRawIterator<'b> {
         unsafe {
             DBRawIterator {
                 inner: ffi::rocksdb_transaction_create_iterator(self.inner, readopts.handle()),
                 db: PhantomData,
+                readopts: None,
             }
         }
     }
@@ -315,7 +427,7 @@ impl IterateCF for OptimisticTransaction {
     fn get_raw_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBRawIterator<'b>, Error> {
         unsafe {
             Ok(DBRawIterator {
@@ -325,6 +437,7 @@ impl IterateCF for OptimisticTransaction {
                     cf_handle.inner,
                 ),
                 db: PhantomData,
+                readopts: None,
             })
         }
     }
@@ -348,6 +461,7 @@ impl PutCF<()> for OptimisticTransaction {
         let key_len = key.len() as size_t;
         let val_ptr = value.as_ptr() as *const c_char;
         let val_len = value.len() as size_t;
+        self.track_key(cf, key);
 
         unsafe {
             match cf {
@@ -391,6 +505,7 @@ impl MergeCF<()> for OptimisticTransaction {
         let key_len = key.len() as size_t;
         let val_ptr = value.as_ptr() as *const c_char;
         let val_len = value.len() as size_t;
+        self.track_key(cf, key);
 
         unsafe {
             match cf {
@@ -429,6 +544,7 @@ impl DeleteCF<()> for OptimisticTransaction {
         let key = key.as_ref();
         let key_ptr = key.as_ptr() as *const c_char;
         let key_len = key.len() as size_t;
+        self.track_key(cf, key);
 
         unsafe {
             match cf {
@@ -464,6 +580,16 @@ impl ConstHandle<ffi::rocksdb_snapshot_t> for OptimisticTransactionSnapshot<'_>
     }
 }
 
+impl OptimisticTransactionSnapshot<'_> {
+    /// Returns the sequence number of the writes visible through this snapshot,
+    /// or `None` if the transaction was started without a snapshot.
+    pub fn sequence_number(&self) -> Option<u64> {
+        let mut seq = 0;
+        unsafe { ffi::rocksdb_snapshot_try_get_sequence_number(self.inner, &mut seq) != 0 }
+            .then_some(seq)
+    }
+}
+
 impl Read for OptimisticTransactionSnapshot<'_> {}
 
 impl GetCF<ReadOptions> for OptimisticTransactionSnapshot<'_> {
@@ -520,10 +646,10 @@ impl Drop for OptimisticTransactionSnapshot<'_> {
 }
 
 impl Iterate for OptimisticTransactionSnapshot<'_> {
-    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &'b ReadOptions) -> DBRawIterator<'b> {
         let mut readopts = readopts.to_owned();
         readopts.set_snapshot(self);
-        self.txn.get_raw_iter(&readopts)
+        owned_raw_iter(self.txn, readopts)
     }
 }
 
@@ -531,11 +657,11 @@ impl IterateCF for OptimisticTransactionSnapshot<'_> {
     fn get_raw_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBRawIterator<'b>, Error> {
         let mut readopts = readopts.to_owned();
         readopts.set_snapshot(self);
-        self.txn.get_raw_iter_cf(cf_handle, &readopts)
+        owned_raw_iter_cf(self.txn, cf_handle, readopts)
     }
 }
 
@@ -553,40 +679,7 @@ impl<'a> GetPinnedCF<'a> for OptimisticTransaction {
 
         let ro_handle = ReadOptions::input_or_default(readopts, &mut default_readopts)?;
 
-        let key = key.as_ref();
-        let key_ptr = key.as_ptr() as *const c_char;
-        let key_len = key.len() as size_t;
-
-        unsafe {
-            let mut err: *mut ::libc::c_char = ::std::ptr::null_mut();
-            let val = match cf {
-                Some(cf) => ffi::rocksdb_transaction_get_pinned_cf(
-                    self.handle(),
-                    ro_handle,
-                    cf.handle(),
-                    key_ptr,
-                    key_len,
-                    &mut err,
-                ),
-                None => ffi::rocksdb_transaction_get_pinned(
-                    self.handle(),
-                    ro_handle,
-                    key_ptr,
-                    key_len,
-                    &mut err,
-                ),
-            };
-
-            if !err.is_null() {
-                return Err(Error::new(ffi_util::error_message(err)));
-            }
-
-            if val.is_null() {
-                Ok(None)
-            } else {
-                Ok(Some(DBPinnableSlice::from_c(val)))
-            }
-        }
+        unsafe { get_pinned_cf_raw(self.handle(), ro_handle, cf, key.as_ref()) }
     }
 }
 
@@ -603,39 +696,6 @@ impl<'a> GetPinnedCF<'a> for OptimisticTransactionSnapshot<'a> {
         let mut ro = readopts.cloned().unwrap_or_default();
         ro.set_snapshot(self);
 
-        let key = key.as_ref();
-        let key_ptr = key.as_ptr() as *const c_char;
-        let key_len = key.len() as size_t;
-
-        unsafe {
-            let mut err: *mut ::libc::c_char = ::std::ptr::null_mut();
-            let val = match cf {
-                Some(cf) => ffi::rocksdb_transaction_get_pinned_cf(
-                    self.txn.handle(),
-                    ro.handle(),
-                    cf.handle(),
-                    key_ptr,
-                    key_len,
-                    &mut err,
-                ),
-                None => ffi::rocksdb_transaction_get_pinned(
-                    self.txn.handle(),
-                    ro.handle(),
-                    key_ptr,
-                    key_len,
-                    &mut err,
-                ),
-            };
-
-            if !err.is_null() {
-                return Err(Error::new(ffi_util::error_message(err)));
-            }
-
-            if val.is_null() {
-                Ok(None)
-            } else {
-                Ok(Some(DBPinnableSlice::from_c(val)))
-            }
-        }
+        unsafe { get_pinned_cf_raw(self.txn.handle(), ro.handle(), cf, key.as_ref()) }
     }
 }
@@ -132,7 +132,7 @@ impl OptimisticTransactionDB {
                 tx_options.inner,
                 ptr::null_mut(),
             );
-            OptimisticTransaction::new(inner)
+            OptimisticTransaction::new(inner, self.base_db)
         }
     }
 
@@ -221,11 +221,12 @@ impl Handle<ffi::rocksdb_t> for OptimisticTransactionDB {
 }
 
 impl Iterate for OptimisticTransactionDB {
-    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &'b ReadOptions) -> DBRawIterator<'b> {
         unsafe {
             DBRawIterator {
                 inner: ffi::rocksdb_create_iterator(self.base_db, readopts.handle()),
                 db: PhantomData,
+                readopts: None,
             }
         }
     }
@@ -235,7 +236,7 @@ impl IterateCF for OptimisticTransactionDB {
     fn get_raw_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBRawIterator<'b>, Error> {
         unsafe {
             Ok(DBRawIterator {
@@ -245,6 +246,7 @@ impl IterateCF for OptimisticTransactionDB {
                     cf_handle.inner,
                 ),
                 db: PhantomData,
+                readopts: None,
             })
         }
     }
@@ -271,6 +273,13 @@ impl ConstHandle<ffi::rocksdb_snapshot_t> for Snapshot<'_> {
     }
 }
 
+impl Snapshot<'_> {
+    /// Returns the sequence number of the writes visible through this snapshot.
+    pub fn sequence_number(&self) -> u64 {
+        unsafe { ffi::rocksdb_snapshot_get_sequence_number(self.inner) }
+    }
+}
+
 impl Read for Snapshot<'_> {}
 
 impl GetCF<ReadOptions> for Snapshot<'_> {
@@ -330,10 +339,10 @@ impl Drop for Snapshot<'_> {
 }
 
 impl Iterate for Snapshot<'_> {
-    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &'b ReadOptions) -> DBRawIterator<'b> {
         let mut ro = readopts.to_owned();
         ro.set_snapshot(self);
-        self.db.get_raw_iter(&ro)
+        owned_raw_iter(self.db, ro)
     }
 }
 
@@ -341,10 +350,10 @@ impl IterateCF for Snapshot<'_> {
     fn get_raw_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBRawIterator<'b>, Error> {
         let mut ro = readopts.to_owned();
         ro.set_snapshot(self);
-        self.db.get_raw_iter_cf(cf_handle, &ro)
+        owned_raw_iter_cf(self.db, cf_handle, ro)
     }
 }
@@ -0,0 +1,109 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use crate::ffi;
+
+use libc::{c_int, c_uchar, c_void};
+use std::ffi::CStr;
+
+/// How much the perf context of the current thread collects.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[repr(i32)]
+pub enum PerfLevel {
+    /// Unknown setting.
+    Uninitialized = 0,
+    /// Disable perf stats.
+    Disable,
+    /// Enable only count stats.
+    EnableCount,
+    /// Count stats and enable time stats except for mutexes.
+    EnableTimeExceptForMutex,
+    /// Other than time, also measure CPU time counters. Still don't measure
+    /// time (neither wall time nor CPU time) for mutexes.
+    EnableTimeAndCPUTimeExceptForMutex,
+    /// Enable count and time stats.
+    EnableTime,
+}
+
+/// Sets the perf level of the current thread.
+pub fn set_perf_level(level: PerfLevel) {
+    unsafe {
+        ffi::rocksdb_set_perf_level(level as c_int);
+    }
+}
+
+/// The perf context of the current thread, counting and timing what the
+/// operations of this thread do inside RocksDB once `set_perf_level` enabled it.
+///
+/// It is tied to the thread which created it, so it is neither `Send` nor `Sync`.
+///
+/// ```
+/// use ckb_rocksdb::{prelude::*, perf::{set_perf_level, PerfContext, PerfLevel}};
+/// # use ckb_rocksdb::TemporaryDBPath;
+///
+/// let path = "_path_for_rocksdb_storage_perf";
+/// # let path = TemporaryDBPath::new();
+/// # {
+/// let db = DB::open_default(&path).unwrap();
+/// set_perf_level(PerfLevel::EnableTime);
+/// let mut ctx = PerfContext::new();
+/// ctx.reset();
+/// db.get(b"k1").unwrap();
+/// println!("{}", ctx.report(true));
+/// # }
+/// ```
+pub struct PerfContext {
+    inner: *mut ffi::rocksdb_perfcontext_t,
+}
+
+impl Default for PerfContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PerfContext {
+    pub fn new() -> Self {
+        Self {
+            inner: unsafe { ffi::rocksdb_perfcontext_create() },
+        }
+    }
+
+    /// Resets all counters to zero.
+    pub fn reset(&mut self) {
+        unsafe {
+            ffi::rocksdb_perfcontext_reset(self.inner);
+        }
+    }
+
+    /// Returns the counters in a human readable form, leaving out the ones
+    /// still at zero when `exclude_zero` is set.
+    pub fn report(&self, exclude_zero: bool) -> String {
+        unsafe {
+            let ptr = ffi::rocksdb_perfcontext_report(self.inner, exclude_zero as c_uchar);
+            let report = CStr::from_ptr(ptr).to_string_lossy().into_owned();
+            ffi::rocksdb_free(ptr as *mut c_void);
+            report
+        }
+    }
+}
+
+impl Drop for PerfContext {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_perfcontext_destroy(self.inner);
+        }
+    }
+}
@@ -14,9 +14,10 @@
 //
 
 use crate::ffi;
+use crate::ops::{Open, OpenCF};
 
 use crate::{
-    ColumnFamily, Error,
+    ColumnFamily, ColumnFamilyDescriptor, Error, Options,
     db_iterator::DBRawIterator,
     db_options::{OptionsMustOutliveDB, ReadOptions},
     handle::Handle,
@@ -41,6 +42,49 @@ impl ReadOnlyDB {
     pub fn path(&self) -> &Path {
         self.path.as_path()
     }
+
+    /// Open the database at `path` without taking the write lock.
+    ///
+    /// When `error_if_log_file_exist` is set, opening fails if the WAL still holds
+    /// entries that were never flushed, instead of silently ignoring them.
+    pub fn open_for_read_only<P: AsRef<Path>>(
+        opts: &Options,
+        path: P,
+        error_if_log_file_exist: bool,
+    ) -> Result<Self, Error> {
+        Self::open_with_descriptor(
+            opts,
+            path,
+            ReadOnlyOpenDescriptor {
+                error_if_log_file_exists: error_if_log_file_exist,
+            },
+        )
+    }
+
+    /// Same as `open_for_read_only`, opening the given column families.
+    pub fn open_cf_for_read_only<P, I, N>(
+        opts: &Options,
+        path: P,
+        cfs: I,
+        error_if_log_file_exist: bool,
+    ) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+        I: IntoIterator<Item = N>,
+        N: AsRef<str>,
+    {
+        let cfs = cfs
+            .into_iter()
+            .map(|name| ColumnFamilyDescriptor::new(name.as_ref(), Options::default()));
+        Self::open_cf_descriptors_with_descriptor(
+            opts,
+            path,
+            cfs,
+            ReadOnlyOpenDescriptor {
+                error_if_log_file_exists: error_if_log_file_exist,
+            },
+        )
+    }
 }
 
 #[derive(Default)]
@@ -110,11 +154,12 @@ impl Handle<ffi::rocksdb_t> for ReadOnlyDB {
 }
 
 impl ops::Iterate for ReadOnlyDB {
-    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &'b ReadOptions) -> DBRawIterator<'b> {
         unsafe {
             DBRawIterator {
                 inner: ffi::rocksdb_create_iterator(self.inner, readopts.handle()),
                 db: PhantomData,
+                readopts: None,
             }
         }
     }
@@ -124,7 +169,7 @@ impl ops::IterateCF for ReadOnlyDB {
     fn get_raw_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBRawIterator<'b>, Error> {
         unsafe {
             Ok(DBRawIterator {
@@ -134,6 +179,7 @@ impl ops::IterateCF for ReadOnlyDB {
                     cf_handle.inner,
                 ),
                 db: PhantomData,
+                readopts: None,
             })
         }
     }
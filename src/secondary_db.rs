@@ -15,9 +15,10 @@
 
 use crate::ffi;
 use crate::ffi_util;
+use crate::ops::{Open, OpenCF};
 
 use crate::{
-    ColumnFamily, Error,
+    ColumnFamily, ColumnFamilyDescriptor, Error, Options,
     db_iterator::DBRawIterator,
     db_options::{OptionsMustOutliveDB, ReadOptions},
     handle::Handle,
@@ -42,6 +43,51 @@ impl SecondaryDB {
         self.path.as_path()
     }
 
+    /// Open a secondary instance that follows the primary at `primary_path`.
+    ///
+    /// The secondary keeps its own info logs under `secondary_path` and only
+    /// sees writes made by the primary after `try_catch_up_with_primary`.
+    pub fn open_as_secondary<P, S>(
+        opts: &Options,
+        primary_path: P,
+        secondary_path: S,
+    ) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+        S: AsRef<Path>,
+    {
+        Self::open_with_descriptor(
+            opts,
+            primary_path,
+            SecondaryOpenDescriptor::new(secondary_path.as_ref().to_string_lossy().into_owned()),
+        )
+    }
+
+    /// Same as `open_as_secondary`, opening the given column families of the primary.
+    pub fn open_cf_as_secondary<P, S, I, N>(
+        opts: &Options,
+        primary_path: P,
+        secondary_path: S,
+        cfs: I,
+    ) -> Result<Self, Error>
+    where
+        P: AsRef<Path>,
+        S: AsRef<Path>,
+        I: IntoIterator<Item = N>,
+        N: AsRef<str>,
+    {
+        let cfs = cfs
+            .into_iter()
+            .map(|name| ColumnFamilyDescriptor::new(name.as_ref(), Options::default()));
+        Self::open_cf_descriptors_with_descriptor(
+            opts,
+            primary_path,
+            cfs,
+            SecondaryOpenDescriptor::new(secondary_path.as_ref().to_string_lossy().into_owned()),
+        )
+    }
+
+    /// Replay the primary's MANIFEST and WAL so that its latest writes become visible.
     pub fn try_catch_up_with_primary(&self) -> Result<(), Error> {
         unsafe { ffi_try!(ffi::rocksdb_try_catch_up_with_primary(self.inner,)) };
         Ok(())
@@ -136,11 +182,12 @@ impl Handle<ffi::rocksdb_t> for SecondaryDB {
 }
 
 impl ops::Iterate for SecondaryDB {
-    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &'b ReadOptions) -> DBRawIterator<'b> {
         unsafe {
             DBRawIterator {
                 inner: ffi::rocksdb_create_iterator(self.inner, readopts.handle()),
                 db: PhantomData,
+                readopts: None,
             }
         }
     }
@@ -150,7 +197,7 @@ impl ops::IterateCF for SecondaryDB {
     fn get_raw_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBRawIterator<'b>, Error> {
         unsafe {
             Ok(DBRawIterator {
@@ -160,6 +207,7 @@ impl ops::IterateCF for SecondaryDB {
                     cf_handle.inner,
                 ),
                 db: PhantomData,
+                readopts: None,
             })
         }
     }
@@ -66,6 +66,14 @@ impl SliceTransform {
         }
     }
 
+    /// Creates a transform taking at most the first `len` bytes of a key,
+    /// shorter keys being their own prefix.
+    pub fn create_capped_prefix(len: size_t) -> SliceTransform {
+        SliceTransform {
+            inner: unsafe { ffi::rocksdb_slicetransform_create_capped_prefix(len) },
+        }
+    }
+
     pub fn create_noop() -> SliceTransform {
         SliceTransform {
             inner: unsafe { ffi::rocksdb_slicetransform_create_noop() },
@@ -14,7 +14,9 @@
 //
 
 use crate::{
-    ColumnFamily, DB, DBRawIterator, DBVector, Error, ReadOptions, handle::ConstHandle, ops::*,
+    ColumnFamily, DB, DBPinnableSlice, DBRawIterator, DBVector, Error, ReadOptions,
+    handle::{ConstHandle, Handle},
+    ops::{get_pinned_cf_raw, *},
 };
 
 /// A consistent view of the database at the point of creation.
@@ -44,6 +46,13 @@ impl ConstHandle<ffi::rocksdb_snapshot_t> for Snapshot<'_> {
     }
 }
 
+impl Snapshot<'_> {
+    /// Returns the sequence number of the writes visible through this snapshot.
+    pub fn sequence_number(&self) -> u64 {
+        unsafe { ffi::rocksdb_snapshot_get_sequence_number(self.inner) }
+    }
+}
+
 impl Read for Snapshot<'_> {}
 
 impl GetCF<ReadOptions> for Snapshot<'_> {
@@ -94,6 +103,23 @@ impl MultiGetCF<ReadOptions> for Snapshot<'_> {
     }
 }
 
+impl<'a> GetPinnedCF<'a> for Snapshot<'a> {
+    type ColumnFamily = &'a ColumnFamily;
+    type ReadOptions = &'a ReadOptions;
+
+    fn get_pinned_cf_full<K: AsRef<[u8]>>(
+        &'a self,
+        cf: Option<Self::ColumnFamily>,
+        key: K,
+        readopts: Option<Self::ReadOptions>,
+    ) -> Result<Option<DBPinnableSlice<'a>>, Error> {
+        let mut ro = readopts.cloned().unwrap_or_default();
+        ro.set_snapshot(self);
+
+        unsafe { get_pinned_cf_raw(self.db.handle(), ro.handle(), cf, key.as_ref()) }
+    }
+}
+
 impl Drop for Snapshot<'_> {
     fn drop(&mut self) {
         unsafe {
@@ -103,10 +129,10 @@ impl Drop for Snapshot<'_> {
 }
 
 impl Iterate for Snapshot<'_> {
-    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &'b ReadOptions) -> DBRawIterator<'b> {
         let mut ro = readopts.to_owned();
         ro.set_snapshot(self);
-        self.db.get_raw_iter(&ro)
+        owned_raw_iter(self.db, ro)
     }
 }
 
@@ -114,10 +140,10 @@ impl IterateCF for Snapshot<'_> {
     fn get_raw_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBRawIterator<'b>, Error> {
         let mut ro = readopts.to_owned();
         ro.set_snapshot(self);
-        self.db.get_raw_iter_cf(cf_handle, &ro)
+        owned_raw_iter_cf(self.db, cf_handle, ro)
     }
 }
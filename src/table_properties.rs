@@ -0,0 +1,223 @@
+use crate::ffi;
+
+use libc::{c_char, c_uchar, c_void, size_t};
+use std::ffi::CStr;
+use std::marker::PhantomData;
+use std::slice;
+use std::sync::Arc;
+
+/// Properties of a SST file, as passed to the table filter set through
+/// [`ReadOptions::set_table_filter`](crate::ReadOptions::set_table_filter).
+pub struct TableProperties<'a> {
+    inner: *const ffi::rocksdb_tableproperties_t,
+    _marker: PhantomData<&'a ()>,
+}
+
+impl TableProperties<'_> {
+    /// Returns the number of entries in the table.
+    pub fn num_entries(&self) -> u64 {
+        unsafe { ffi::rocksdb_tableproperties_num_entries(self.inner) }
+    }
+
+    /// Returns the number of deletions in the table.
+    pub fn num_deletions(&self) -> u64 {
+        unsafe { ffi::rocksdb_tableproperties_num_deletions(self.inner) }
+    }
+
+    /// Returns the number of data blocks in the table.
+    pub fn num_data_blocks(&self) -> u64 {
+        unsafe { ffi::rocksdb_tableproperties_num_data_blocks(self.inner) }
+    }
+
+    /// Returns the total size of all data blocks.
+    pub fn data_size(&self) -> u64 {
+        unsafe { ffi::rocksdb_tableproperties_data_size(self.inner) }
+    }
+
+    /// Returns the total raw size of the keys.
+    pub fn raw_key_size(&self) -> u64 {
+        unsafe { ffi::rocksdb_tableproperties_raw_key_size(self.inner) }
+    }
+
+    /// Returns the total raw size of the values.
+    pub fn raw_value_size(&self) -> u64 {
+        unsafe { ffi::rocksdb_tableproperties_raw_value_size(self.inner) }
+    }
+
+    /// Returns the name of the column family the table belongs to.
+    pub fn column_family_name(&self) -> &[u8] {
+        unsafe {
+            let mut name_len: size_t = 0;
+            let name = ffi::rocksdb_tableproperties_column_family_name(self.inner, &mut name_len);
+            slice::from_raw_parts(name as *const u8, name_len)
+        }
+    }
+
+    /// Returns a property added to the table by a properties collector.
+    pub fn user_collected_property(&self, name: &str) -> Option<&[u8]> {
+        unsafe {
+            let mut value_len: size_t = 0;
+            let value = ffi::rocksdb_tableproperties_user_collected_property(
+                self.inner,
+                name.as_ptr() as *const c_char,
+                name.len() as size_t,
+                &mut value_len,
+            );
+            if value.is_null() {
+                None
+            } else {
+                Some(slice::from_raw_parts(value as *const u8, value_len))
+            }
+        }
+    }
+}
+
+pub(crate) type TableFilterFn = Arc<dyn Fn(&TableProperties<'_>) -> bool + Send + Sync>;
+
+pub(crate) unsafe extern "C" fn table_filter_destructor_callback(raw_cb: *mut c_void) {
+    unsafe {
+        let _ = Box::from_raw(raw_cb as *mut TableFilterFn);
+    }
+}
+
+pub(crate) unsafe extern "C" fn table_filter_callback(
+    raw_cb: *mut c_void,
+    props: *const ffi::rocksdb_tableproperties_t,
+) -> c_uchar {
+    unsafe {
+        let cb = &*(raw_cb as *mut TableFilterFn);
+        let props = TableProperties {
+            inner: props,
+            _marker: PhantomData,
+        };
+        cb(&props) as c_uchar
+    }
+}
+
+/// Gathers user properties about a SST file while it is built, which are
+/// stored in the file and can then be read back through
+/// [`TableProperties::user_collected_property`], e.g. from a table filter.
+///
+/// See [Options::add_table_properties_collector_factory][add_factory].
+///
+/// [add_factory]: ../struct.Options.html#method.add_table_properties_collector_factory
+pub trait TablePropertiesCollector: Send {
+    /// Called with each key put into the table, in order. Deletions and
+    /// merges are not passed.
+    fn add(&mut self, key: &[u8], value: &[u8]);
+
+    /// Called once the table is complete, returns the properties to store.
+    fn finish(&mut self) -> Vec<(Vec<u8>, Vec<u8>)>;
+
+    /// Returns a name that identifies this collector.
+    fn name(&self) -> &CStr;
+}
+
+/// Creates a `TablePropertiesCollector` for each SST file written by a
+/// flush or a compaction. Several files may be written at once, from
+/// different background threads.
+pub trait TablePropertiesCollectorFactory: Send + Sync {
+    type Collector: TablePropertiesCollector;
+
+    /// Returns a collector for a new file of the given column family.
+    fn create(&self, column_family_id: u32) -> Self::Collector;
+
+    /// Returns a name that identifies this factory.
+    fn name(&self) -> &CStr;
+}
+
+pub(crate) unsafe extern "C" fn collector_destructor_callback<C>(raw_self: *mut c_void)
+where
+    C: TablePropertiesCollector,
+{
+    unsafe {
+        let _ = Box::from_raw(raw_self as *mut C);
+    }
+}
+
+pub(crate) unsafe extern "C" fn collector_add_callback<C>(
+    raw_self: *mut c_void,
+    key: *const c_char,
+    key_length: size_t,
+    value: *const c_char,
+    value_length: size_t,
+) where
+    C: TablePropertiesCollector,
+{
+    unsafe {
+        let self_ = &mut *(raw_self as *mut C);
+        let key = slice::from_raw_parts(key as *const u8, key_length);
+        let value = slice::from_raw_parts(value as *const u8, value_length);
+        self_.add(key, value);
+    }
+}
+
+pub(crate) unsafe extern "C" fn collector_finish_callback<C>(
+    raw_self: *mut c_void,
+    props: *mut ffi::rocksdb_usercollectedproperties_t,
+) where
+    C: TablePropertiesCollector,
+{
+    unsafe {
+        let self_ = &mut *(raw_self as *mut C);
+        for (name, value) in self_.finish() {
+            ffi::rocksdb_usercollectedproperties_add(
+                props,
+                name.as_ptr() as *const c_char,
+                name.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            );
+        }
+    }
+}
+
+pub(crate) unsafe extern "C" fn collector_name_callback<C>(raw_self: *mut c_void) -> *const c_char
+where
+    C: TablePropertiesCollector,
+{
+    unsafe {
+        let self_ = &*(raw_self as *const C);
+        self_.name().as_ptr()
+    }
+}
+
+pub(crate) unsafe extern "C" fn factory_destructor_callback<F>(raw_self: *mut c_void)
+where
+    F: TablePropertiesCollectorFactory,
+{
+    unsafe {
+        let _ = Box::from_raw(raw_self as *mut F);
+    }
+}
+
+pub(crate) unsafe extern "C" fn factory_create_callback<F>(
+    raw_self: *mut c_void,
+    column_family_id: u32,
+) -> *mut ffi::rocksdb_tablepropertiescollector_t
+where
+    F: TablePropertiesCollectorFactory,
+{
+    unsafe {
+        let self_ = &*(raw_self as *const F);
+        let collector = Box::new(self_.create(column_family_id));
+
+        ffi::rocksdb_tablepropertiescollector_create(
+            Box::into_raw(collector) as *mut c_void,
+            Some(collector_destructor_callback::<F::Collector>),
+            Some(collector_add_callback::<F::Collector>),
+            Some(collector_finish_callback::<F::Collector>),
+            Some(collector_name_callback::<F::Collector>),
+        )
+    }
+}
+
+pub(crate) unsafe extern "C" fn factory_name_callback<F>(raw_self: *mut c_void) -> *const c_char
+where
+    F: TablePropertiesCollectorFactory,
+{
+    unsafe {
+        let self_ = &*(raw_self as *const F);
+        self_.name().as_ptr()
+    }
+}
@@ -1,12 +1,13 @@
 use crate::ffi;
 use crate::{
-    ColumnFamily, DBPinnableSlice, DBRawIterator, DBVector, Error, ReadOptions, ffi_util,
+    ColumnFamily, DBPinnableSlice, DBRawIterator, DBVector, Error, ReadOptions,
     handle::{ConstHandle, Handle},
-    ops::*,
+    ops::{get_pinned_cf_raw, *},
 };
 use libc::{c_char, c_uchar, c_void, size_t};
 use std::marker::PhantomData;
 use std::ptr;
+use std::slice;
 
 pub struct Transaction<'a, T> {
     inner: *mut ffi::rocksdb_transaction_t,
@@ -46,6 +47,50 @@ impl<'a, T> Transaction<'a, T> {
         unsafe { ffi::rocksdb_transaction_set_savepoint(self.inner) }
     }
 
+    /// Prepares the transaction for a two-phase commit.
+    ///
+    /// The transaction must have been named with `set_name` first. Once
+    /// prepared, its writes are persisted in the WAL and it survives a
+    /// restart until it is committed or rolled back.
+    pub fn prepare(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_transaction_prepare(self.inner,));
+        }
+        Ok(())
+    }
+
+    /// Sets the name of the transaction, required before `prepare`.
+    ///
+    /// Names must be unique among the live transactions of a database.
+    pub fn set_name(&self, name: &[u8]) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_transaction_set_name(
+                self.inner,
+                name.as_ptr() as *const c_char,
+                name.len() as size_t,
+            ));
+        }
+        Ok(())
+    }
+
+    /// Returns the name of the transaction, or `None` if it is unnamed.
+    pub fn get_name(&self) -> Option<Vec<u8>> {
+        unsafe {
+            let mut name_len: size_t = 0;
+            let name = ffi::rocksdb_transaction_get_name(self.inner, &mut name_len);
+            if name.is_null() {
+                return None;
+            }
+            let result = slice::from_raw_parts(name as *const u8, name_len).to_vec();
+            ffi::rocksdb_free(name as *mut c_void);
+            if result.is_empty() {
+                None
+            } else {
+                Some(result)
+            }
+        }
+    }
+
     /// Get Snapshot
     pub fn snapshot(&'a self) -> TransactionSnapshot<'a, T> {
         unsafe {
@@ -132,6 +177,194 @@ impl<'a, T> Transaction<'a, T> {
             }
         }
     }
+
+    /// Same as `get_for_update`, returning the value pinned instead of copied.
+    pub fn get_pinned_for_update<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+    ) -> Result<Option<DBPinnableSlice<'_>>, Error> {
+        let opt = ReadOptions::default();
+        self.get_pinned_for_update_opt(key, &opt, true)
+    }
+
+    /// Same as `get_for_update_opt`, returning the value pinned instead of copied.
+    pub fn get_pinned_for_update_opt<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        readopts: &ReadOptions,
+        exclusive: bool,
+    ) -> Result<Option<DBPinnableSlice<'_>>, Error> {
+        let key = key.as_ref();
+        let key_ptr = key.as_ptr() as *const c_char;
+        let key_len = key.len() as size_t;
+        unsafe {
+            let val = ffi_try!(ffi::rocksdb_transaction_get_pinned_for_update(
+                self.handle(),
+                readopts.handle(),
+                key_ptr,
+                key_len,
+                exclusive as c_uchar,
+            ));
+
+            if val.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(DBPinnableSlice::from_c(val)))
+            }
+        }
+    }
+
+    /// Same as `get_for_update_cf`, returning the value pinned instead of copied.
+    pub fn get_pinned_for_update_cf<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        key: K,
+    ) -> Result<Option<DBPinnableSlice<'_>>, Error> {
+        let opt = ReadOptions::default();
+        self.get_pinned_for_update_cf_opt(cf, key, &opt, true)
+    }
+
+    /// Same as `get_for_update_cf_opt`, returning the value pinned instead of copied.
+    pub fn get_pinned_for_update_cf_opt<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        key: K,
+        readopts: &ReadOptions,
+        exclusive: bool,
+    ) -> Result<Option<DBPinnableSlice<'_>>, Error> {
+        let key = key.as_ref();
+        let key_ptr = key.as_ptr() as *const c_char;
+        let key_len = key.len() as size_t;
+        unsafe {
+            let val = ffi_try!(ffi::rocksdb_transaction_get_pinned_for_update_cf(
+                self.handle(),
+                readopts.handle(),
+                cf.handle(),
+                key_ptr,
+                key_len,
+                exclusive as c_uchar,
+            ));
+
+            if val.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(DBPinnableSlice::from_c(val)))
+            }
+        }
+    }
+
+    /// Get For Update without validating the key against the transaction
+    /// snapshot.
+    ///
+    /// The key is still locked and tracked by the transaction, only the
+    /// check that it wasn't written after the transaction snapshot is
+    /// skipped. The read uses default `ReadOptions`, so it returns the latest
+    /// committed value, which may be newer than the snapshot.
+    pub fn get_for_update_no_validate<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+    ) -> Result<Option<DBVector>, Error> {
+        let opt = ReadOptions::default();
+        self.get_for_update_no_validate_opt(key, &opt, true)
+    }
+
+    /// Same as `get_for_update_no_validate` with custom ReadOptions and exclusive
+    pub fn get_for_update_no_validate_opt<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        readopts: &ReadOptions,
+        exclusive: bool,
+    ) -> Result<Option<DBVector>, Error> {
+        self.get_for_update_validate_full(None, key.as_ref(), readopts, exclusive, false)
+    }
+
+    /// Same as `get_for_update_no_validate` for a given column family
+    pub fn get_for_update_cf_no_validate<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        key: K,
+    ) -> Result<Option<DBVector>, Error> {
+        let opt = ReadOptions::default();
+        self.get_for_update_cf_no_validate_opt(cf, key, &opt, true)
+    }
+
+    /// Same as `get_for_update_no_validate_opt` for a given column family
+    pub fn get_for_update_cf_no_validate_opt<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        key: K,
+        readopts: &ReadOptions,
+        exclusive: bool,
+    ) -> Result<Option<DBVector>, Error> {
+        self.get_for_update_validate_full(Some(cf), key.as_ref(), readopts, exclusive, false)
+    }
+
+    /// Get For Update for several keys, locking each of them.
+    ///
+    /// Keys are read one after another with a shared default `ReadOptions`,
+    /// and the results are returned in the order of `keys`.
+    pub fn multi_get_for_update<K, I>(
+        &self,
+        keys: I,
+        exclusive: bool,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        let opt = ReadOptions::default();
+        keys.into_iter()
+            .map(|key| self.get_for_update_validate_full(None, key.as_ref(), &opt, exclusive, true))
+            .collect()
+    }
+
+    /// Same as `multi_get_for_update` for a given column family
+    pub fn multi_get_for_update_cf<K, I>(
+        &self,
+        cf: &ColumnFamily,
+        keys: I,
+        exclusive: bool,
+    ) -> Vec<Result<Option<DBVector>, Error>>
+    where
+        K: AsRef<[u8]>,
+        I: IntoIterator<Item = K>,
+    {
+        let opt = ReadOptions::default();
+        keys.into_iter()
+            .map(|key| {
+                self.get_for_update_validate_full(Some(cf), key.as_ref(), &opt, exclusive, true)
+            })
+            .collect()
+    }
+
+    fn get_for_update_validate_full(
+        &self,
+        cf: Option<&ColumnFamily>,
+        key: &[u8],
+        readopts: &ReadOptions,
+        exclusive: bool,
+        do_validate: bool,
+    ) -> Result<Option<DBVector>, Error> {
+        unsafe {
+            let mut val_len: size_t = 0;
+            let val = ffi_try!(ffi::rocksdb_transaction_get_for_update_validate_cf(
+                self.handle(),
+                readopts.handle(),
+                cf.map_or(ptr::null_mut(), |cf| cf.inner),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                &mut val_len,
+                exclusive as c_uchar,
+                do_validate as c_uchar,
+            )) as *mut u8;
+
+            if val.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(DBVector::from_c(val, val_len)))
+            }
+        }
+    }
 }
 
 impl<T> Drop for Transaction<'_, T> {
@@ -228,6 +461,9 @@ where
             .into_iter()
             .map(|k| (Box::from(k.as_ref()), k.as_ref().len()))
             .unzip();
+        if keys.is_empty() {
+            return Vec::new();
+        }
         let ptr_keys: Vec<_> = keys.iter().map(|k| k.as_ptr() as *const c_char).collect();
 
         let mut values = vec![ptr::null_mut(); keys.len()];
@@ -279,6 +515,9 @@ where
             .into_iter()
             .map(|(cf, key)| ((cf, Box::from(key.as_ref())), key.as_ref().len()))
             .unzip();
+        if cfs_and_keys.is_empty() {
+            return Vec::new();
+        }
         let ptr_keys: Vec<_> = cfs_and_keys
             .iter()
             .map(|(_, k)| k.as_ptr() as *const c_char)
@@ -310,11 +549,12 @@ where
 }
 
 impl<T> Iterate for Transaction<'_, T> {
-    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &'b ReadOptions) -> DBRawIterator<'b> {
         unsafe {
             DBRawIterator {
                 inner: ffi::rocksdb_transaction_create_iterator(self.inner, readopts.handle()),
                 db: PhantomData,
+                readopts: None,
             }
         }
     }
@@ -324,7 +564,7 @@ impl<T> IterateCF for Transaction<'_, T> {
     fn get_raw_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBRawIterator<'b>, Error> {
         unsafe {
             Ok(DBRawIterator {
@@ -334,6 +574,7 @@ impl<T> IterateCF for Transaction<'_, T> {
                     cf_handle.inner,
                 ),
                 db: PhantomData,
+                readopts: None,
             })
         }
     }
@@ -350,6 +591,16 @@ impl<T> ConstHandle<ffi::rocksdb_snapshot_t> for TransactionSnapshot<'_, T> {
     }
 }
 
+impl<T> TransactionSnapshot<'_, T> {
+    /// Returns the sequence number of the writes visible through this snapshot,
+    /// or `None` if the transaction was started without a snapshot.
+    pub fn sequence_number(&self) -> Option<u64> {
+        let mut seq = 0;
+        unsafe { ffi::rocksdb_snapshot_try_get_sequence_number(self.inner, &mut seq) != 0 }
+            .then_some(seq)
+    }
+}
+
 impl<T> Read for TransactionSnapshot<'_, T> {}
 
 impl<'a, T> GetCF<ReadOptions> for TransactionSnapshot<'a, T>
@@ -535,10 +786,10 @@ impl<T> Drop for TransactionSnapshot<'_, T> {
 }
 
 impl<T: Iterate> Iterate for TransactionSnapshot<'_, T> {
-    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &'b ReadOptions) -> DBRawIterator<'b> {
         let mut readopts = readopts.to_owned();
         readopts.set_snapshot(self);
-        self.db.get_raw_iter(&readopts)
+        owned_raw_iter(self.db, readopts)
     }
 }
 
@@ -546,11 +797,11 @@ impl<T: IterateCF> IterateCF for TransactionSnapshot<'_, T> {
     fn get_raw_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBRawIterator<'b>, Error> {
         let mut readopts = readopts.to_owned();
         readopts.set_snapshot(self);
-        self.db.get_raw_iter_cf(cf_handle, &readopts)
+        owned_raw_iter_cf(self.db, cf_handle, readopts)
     }
 }
 
@@ -568,40 +819,7 @@ impl<'a, T> GetPinnedCF<'a> for Transaction<'a, T> {
 
         let ro_handle = ReadOptions::input_or_default(readopts, &mut default_readopts)?;
 
-        let key = key.as_ref();
-        let key_ptr = key.as_ptr() as *const c_char;
-        let key_len = key.len() as size_t;
-
-        unsafe {
-            let mut err: *mut ::libc::c_char = ::std::ptr::null_mut();
-            let val = match cf {
-                Some(cf) => ffi::rocksdb_transaction_get_pinned_cf(
-                    self.handle(),
-                    ro_handle,
-                    cf.handle(),
-                    key_ptr,
-                    key_len,
-                    &mut err,
-                ),
-                None => ffi::rocksdb_transaction_get_pinned(
-                    self.handle(),
-                    ro_handle,
-                    key_ptr,
-                    key_len,
-                    &mut err,
-                ),
-            };
-
-            if !err.is_null() {
-                return Err(Error::new(ffi_util::error_message(err)));
-            }
-
-            if val.is_null() {
-                Ok(None)
-            } else {
-                Ok(Some(DBPinnableSlice::from_c(val)))
-            }
-        }
+        unsafe { get_pinned_cf_raw(self.handle(), ro_handle, cf, key.as_ref()) }
     }
 }
 
@@ -618,39 +836,6 @@ impl<'a, T> GetPinnedCF<'a> for TransactionSnapshot<'a, T> {
         let mut ro = readopts.cloned().unwrap_or_default();
         ro.set_snapshot(self);
 
-        let key = key.as_ref();
-        let key_ptr = key.as_ptr() as *const c_char;
-        let key_len = key.len() as size_t;
-
-        unsafe {
-            let mut err: *mut ::libc::c_char = ::std::ptr::null_mut();
-            let val = match cf {
-                Some(cf) => ffi::rocksdb_transaction_get_pinned_cf(
-                    self.db.handle(),
-                    ro.handle(),
-                    cf.handle(),
-                    key_ptr,
-                    key_len,
-                    &mut err,
-                ),
-                None => ffi::rocksdb_transaction_get_pinned(
-                    self.db.handle(),
-                    ro.handle(),
-                    key_ptr,
-                    key_len,
-                    &mut err,
-                ),
-            };
-
-            if !err.is_null() {
-                return Err(Error::new(ffi_util::error_message(err)));
-            }
-
-            if val.is_null() {
-                Ok(None)
-            } else {
-                Ok(Some(DBPinnableSlice::from_c(val)))
-            }
-        }
+        unsafe { get_pinned_cf_raw(self.db.handle(), ro.handle(), cf, key.as_ref()) }
     }
 }
@@ -10,12 +10,13 @@ use crate::{
 };
 
 use crate::ffi;
-use libc::{c_char, c_uchar, size_t};
+use libc::{c_char, c_uchar, c_void, size_t};
 use std::collections::BTreeMap;
 use std::marker::PhantomData;
 use std::path::Path;
 use std::path::PathBuf;
 use std::ptr;
+use std::slice;
 
 /// A transaction database.
 pub struct TransactionDB {
@@ -29,6 +30,25 @@ impl TransactionDB {
     pub fn path(&self) -> &Path {
         self.path.as_path()
     }
+
+    /// Returns the transactions that were prepared but neither committed
+    /// nor rolled back, typically found when reopening the database after
+    /// a crash. Each of them has to be committed or rolled back.
+    pub fn get_prepared_transactions(&self) -> Vec<Transaction<'_, TransactionDB>> {
+        unsafe {
+            let mut cnt: size_t = 0;
+            let txns = ffi::rocksdb_transactiondb_get_prepared_transactions(self.inner, &mut cnt);
+            if txns.is_null() {
+                return Vec::new();
+            }
+            let result = slice::from_raw_parts(txns, cnt)
+                .iter()
+                .map(|&txn| Transaction::new(txn))
+                .collect();
+            ffi::rocksdb_free(txns as *mut c_void);
+            result
+        }
+    }
 }
 
 impl Handle<ffi::rocksdb_transactiondb_t> for TransactionDB {
@@ -128,11 +148,12 @@ impl TransactionBegin for TransactionDB {
 }
 
 impl Iterate for TransactionDB {
-    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &'b ReadOptions) -> DBRawIterator<'b> {
         unsafe {
             DBRawIterator {
                 inner: ffi::rocksdb_transactiondb_create_iterator(self.inner, readopts.handle()),
                 db: PhantomData,
+                readopts: None,
             }
         }
     }
@@ -142,7 +163,7 @@ impl IterateCF for TransactionDB {
     fn get_raw_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBRawIterator<'b>, Error> {
         unsafe {
             Ok(DBRawIterator {
@@ -152,6 +173,7 @@ impl IterateCF for TransactionDB {
                     cf_handle.handle(),
                 ),
                 db: PhantomData,
+                readopts: None,
             })
         }
     }
@@ -178,7 +200,11 @@ impl TransactionDBOptions {
         }
     }
 
-    pub fn set_default_lock_timeout(&self, default_lock_timeout: i64) {
+    /// Sets the time in milliseconds writes made directly on the database,
+    /// outside a transaction, wait for a lock held by a transaction.
+    ///
+    /// Default: 1000
+    pub fn set_default_lock_timeout(&mut self, default_lock_timeout: i64) {
         unsafe {
             ffi::rocksdb_transactiondb_options_set_default_lock_timeout(
                 self.inner,
@@ -187,15 +213,28 @@ impl TransactionDBOptions {
         }
     }
 
-    pub fn set_max_num_locks(&self, max_num_locks: i64) {
+    /// Sets the maximum number of keys that can be locked at the same time
+    /// per column family. Locking more keys fails with a busy error naming
+    /// the `max_num_locks` limit.
+    ///
+    /// Default: -1, unlimited
+    pub fn set_max_num_locks(&mut self, max_num_locks: i64) {
         unsafe { ffi::rocksdb_transactiondb_options_set_max_num_locks(self.inner, max_num_locks) }
     }
 
-    pub fn set_num_stripes(&self, num_stripes: usize) {
+    /// Sets the number of sub-tables per lock table, increasing this value
+    /// reduces contention on the lock table mutexes.
+    ///
+    /// Default: 16
+    pub fn set_num_stripes(&mut self, num_stripes: usize) {
         unsafe { ffi::rocksdb_transactiondb_options_set_num_stripes(self.inner, num_stripes) }
     }
 
-    pub fn set_transaction_lock_timeout(&self, txn_lock_timeout: i64) {
+    /// Sets the time in milliseconds a transaction waits for a lock when its
+    /// `TransactionOptions` lock timeout is negative.
+    ///
+    /// Default: 1000
+    pub fn set_transaction_lock_timeout(&mut self, txn_lock_timeout: i64) {
         unsafe {
             ffi::rocksdb_transactiondb_options_set_transaction_lock_timeout(
                 self.inner,
@@ -232,7 +271,11 @@ impl TransactionOptions {
         }
     }
 
-    pub fn set_deadlock_detect(&self, deadlock_detect: bool) {
+    /// Detects deadlocks while waiting for a lock, failing the transaction
+    /// with a busy error instead of waiting for the lock timeout.
+    ///
+    /// Default: false
+    pub fn set_deadlock_detect(&mut self, deadlock_detect: bool) {
         unsafe {
             ffi::rocksdb_transaction_options_set_deadlock_detect(
                 self.inner,
@@ -241,19 +284,31 @@ impl TransactionOptions {
         }
     }
 
-    pub fn set_deadlock_detect_depth(&self, depth: i64) {
+    /// Sets how many waiting transactions deadlock detection follows.
+    ///
+    /// Default: 50
+    pub fn set_deadlock_detect_depth(&mut self, depth: i64) {
         unsafe { ffi::rocksdb_transaction_options_set_deadlock_detect_depth(self.inner, depth) }
     }
 
-    pub fn set_expiration(&self, expiration: i64) {
+    /// Sets the time in milliseconds after which the transaction expires,
+    /// other transactions may then steal its locks and it fails to commit.
+    ///
+    /// Default: -1, never expires
+    pub fn set_expiration(&mut self, expiration: i64) {
         unsafe { ffi::rocksdb_transaction_options_set_expiration(self.inner, expiration) }
     }
 
-    pub fn set_lock_timeout(&self, lock_timeout: i64) {
+    /// Sets the time in milliseconds to wait for a lock held by another
+    /// transaction before failing with a timed out error. 0 fails at once,
+    /// a negative value uses the `TransactionDBOptions` transaction lock timeout.
+    ///
+    /// Default: -1
+    pub fn set_lock_timeout(&mut self, lock_timeout: i64) {
         unsafe { ffi::rocksdb_transaction_options_set_lock_timeout(self.inner, lock_timeout) }
     }
 
-    pub fn set_max_write_batch_size(&self, size: usize) {
+    pub fn set_max_write_batch_size(&mut self, size: usize) {
         unsafe { ffi::rocksdb_transaction_options_set_max_write_batch_size(self.inner, size) }
     }
 
@@ -360,6 +415,9 @@ impl MultiGet<ReadOptions> for TransactionDB {
             .into_iter()
             .map(|k| (Box::from(k.as_ref()), k.as_ref().len()))
             .unzip();
+        if keys.is_empty() {
+            return Vec::new();
+        }
         let ptr_keys: Vec<_> = keys.iter().map(|k| k.as_ptr() as *const c_char).collect();
 
         let mut values = vec![ptr::null_mut(); keys.len()];
@@ -408,6 +466,9 @@ impl MultiGetCF<ReadOptions> for TransactionDB {
             .into_iter()
             .map(|(cf, key)| ((cf, Box::from(key.as_ref())), key.as_ref().len()))
             .unzip();
+        if cfs_and_keys.is_empty() {
+            return Vec::new();
+        }
         let ptr_keys: Vec<_> = cfs_and_keys
             .iter()
             .map(|(_, k)| k.as_ptr() as *const c_char)
@@ -617,6 +678,13 @@ impl ConstHandle<ffi::rocksdb_snapshot_t> for Snapshot<'_> {
     }
 }
 
+impl Snapshot<'_> {
+    /// Returns the sequence number of the writes visible through this snapshot.
+    pub fn sequence_number(&self) -> u64 {
+        unsafe { ffi::rocksdb_snapshot_get_sequence_number(self.inner) }
+    }
+}
+
 impl Read for Snapshot<'_> {}
 
 impl GetCF<ReadOptions> for Snapshot<'_> {
@@ -676,10 +744,10 @@ impl Drop for Snapshot<'_> {
 }
 
 impl Iterate for Snapshot<'_> {
-    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &ReadOptions) -> DBRawIterator<'b> {
+    fn get_raw_iter<'a: 'b, 'b>(&'a self, readopts: &'b ReadOptions) -> DBRawIterator<'b> {
         let mut ro = readopts.to_owned();
         ro.set_snapshot(self);
-        self.db.get_raw_iter(&ro)
+        owned_raw_iter(self.db, ro)
     }
 }
 
@@ -687,11 +755,11 @@ impl IterateCF for Snapshot<'_> {
     fn get_raw_iter_cf<'a: 'b, 'b>(
         &'a self,
         cf_handle: &ColumnFamily,
-        readopts: &ReadOptions,
+        readopts: &'b ReadOptions,
     ) -> Result<DBRawIterator<'b>, Error> {
         let mut ro = readopts.to_owned();
         ro.set_snapshot(self);
-        self.db.get_raw_iter_cf(cf_handle, &ro)
+        owned_raw_iter_cf(self.db, cf_handle, ro)
     }
 }
 
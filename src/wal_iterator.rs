@@ -0,0 +1,111 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use crate::ffi;
+
+use crate::{DB, Error, WriteBatch};
+
+use std::marker::PhantomData;
+
+/// An iterator over the write batches recorded in the WAL, created by
+/// `DB::get_updates_since`.
+///
+/// Each item is the sequence number of the first write of a batch together
+/// with the batch. The iterator is only valid while the WAL files it reads
+/// are kept, a flush may let RocksDB archive or delete them.
+///
+/// ```
+/// use ckb_rocksdb::prelude::*;
+/// # use ckb_rocksdb::TemporaryDBPath;
+///
+/// let path = "_path_for_rocksdb_storage_wal_iterator";
+/// # let path = TemporaryDBPath::new();
+/// # {
+/// let db = DB::open_default(&path).unwrap();
+/// let seq = db.latest_sequence_number();
+/// db.put(b"k1", b"v1").unwrap();
+///
+/// for update in db.get_updates_since(seq + 1).unwrap() {
+///     let (seq, batch) = update.unwrap();
+///     println!("{} {}", seq, batch.len());
+/// }
+/// # }
+/// ```
+pub struct WalIterator<'a> {
+    inner: *mut ffi::rocksdb_wal_iterator_t,
+    started: bool,
+    done: bool,
+    db: PhantomData<&'a DB>,
+}
+
+impl<'a> WalIterator<'a> {
+    pub(crate) fn new(db: &'a DB, seq_number: u64) -> Result<WalIterator<'a>, Error> {
+        let inner = unsafe {
+            ffi_try!(ffi::rocksdb_get_updates_since(
+                db.inner,
+                seq_number,
+                std::ptr::null(),
+            ))
+        };
+        Ok(WalIterator {
+            inner,
+            started: false,
+            done: false,
+            db: PhantomData,
+        })
+    }
+
+    fn status(&self) -> Result<(), Error> {
+        unsafe {
+            ffi_try!(ffi::rocksdb_wal_iter_status(self.inner));
+        }
+        Ok(())
+    }
+}
+
+impl Iterator for WalIterator<'_> {
+    type Item = Result<(u64, WriteBatch), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        // the iterator is positioned on the first batch when created
+        unsafe {
+            if self.started {
+                ffi::rocksdb_wal_iter_next(self.inner);
+            }
+            self.started = true;
+
+            if ffi::rocksdb_wal_iter_valid(self.inner) == 0 {
+                self.done = true;
+                return self.status().err().map(Err);
+            }
+
+            let mut seq = 0;
+            let batch = ffi::rocksdb_wal_iter_get_batch(self.inner, &mut seq);
+            Some(Ok((seq, WriteBatch::from_c(batch))))
+        }
+    }
+}
+
+impl Drop for WalIterator<'_> {
+    fn drop(&mut self) {
+        unsafe {
+            ffi::rocksdb_wal_iter_destroy(self.inner);
+        }
+    }
+}
@@ -17,7 +17,9 @@ use crate::ffi;
 
 use crate::{ColumnFamily, Error, handle::Handle};
 
-use libc::{c_char, size_t};
+use libc::{c_char, c_void, size_t};
+use std::ops::Deref;
+use std::slice;
 
 /// An atomic batch of write operations.
 ///
@@ -46,6 +48,11 @@ pub struct WriteBatch {
 }
 
 impl WriteBatch {
+    /// Wraps a batch allocated by RocksDB, taking ownership of it.
+    pub(crate) unsafe fn from_c(inner: *mut ffi::rocksdb_writebatch_t) -> WriteBatch {
+        WriteBatch { inner }
+    }
+
     pub fn len(&self) -> usize {
         unsafe { ffi::rocksdb_writebatch_count(self.inner) as usize }
     }
@@ -222,6 +229,37 @@ impl WriteBatch {
         }
     }
 
+    /// Walks the operations of the batch in order, passing each of them to
+    /// `handler`.
+    ///
+    /// Puts, deletes and merges of every column family are reported. Single
+    /// deletes and range deletes can't be, and stop the iteration there, in
+    /// which case an error telling how many operations were handled is
+    /// returned.
+    pub fn iterate<H: WriteBatchIterator>(&self, handler: &mut H) -> Result<(), Error> {
+        let mut state = IterateState {
+            handler,
+            handled: 0,
+        };
+        unsafe {
+            ffi::rocksdb_writebatch_iterate_cf(
+                self.inner,
+                &mut state as *mut IterateState<H> as *mut c_void,
+                Some(writebatch_put_cf_callback::<H>),
+                Some(writebatch_delete_cf_callback::<H>),
+                Some(writebatch_merge_cf_callback::<H>),
+            );
+        }
+        if state.handled < self.len() {
+            return Err(Error::new(format!(
+                "WriteBatch::iterate stopped at an unsupported operation after {} of {} operations",
+                state.handled,
+                self.len()
+            )));
+        }
+        Ok(())
+    }
+
     /// Clear all updates buffered in this batch.
     pub fn clear(&mut self) -> Result<(), Error> {
         unsafe {
@@ -231,6 +269,183 @@ impl WriteBatch {
     }
 }
 
+/// A `WriteBatch` whose operations all go to a single column family.
+///
+/// It dereferences to the underlying `WriteBatch`, so it can be passed to
+/// `write` directly:
+///
+/// ```
+/// use ckb_rocksdb::{prelude::*, CfWriteBatch};
+/// # use ckb_rocksdb::TemporaryDBPath;
+///
+/// let path = "_path_for_rocksdb_storage_cf_batch";
+/// # let path = TemporaryDBPath::new();
+/// # {
+/// let mut opts = Options::default();
+/// opts.create_if_missing(true);
+/// opts.create_missing_column_families(true);
+/// let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+/// let cf1 = db.cf_handle("cf1").unwrap();
+///
+/// let mut batch = CfWriteBatch::new(cf1);
+/// batch.put(b"my key", b"my value");
+/// batch.delete(b"key2");
+///
+/// db.write(&batch); // Atomically commits the batch into cf1
+/// # }
+/// ```
+pub struct CfWriteBatch<'a> {
+    batch: WriteBatch,
+    cf: &'a ColumnFamily,
+}
+
+impl<'a> CfWriteBatch<'a> {
+    pub fn new(cf: &'a ColumnFamily) -> CfWriteBatch<'a> {
+        CfWriteBatch {
+            batch: WriteBatch::default(),
+            cf,
+        }
+    }
+
+    /// Returns the column family the operations are bound to.
+    pub fn column_family(&self) -> &'a ColumnFamily {
+        self.cf
+    }
+
+    pub fn put<K, V>(&mut self, key: K, value: V) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.batch.put_cf(self.cf, key, value)
+    }
+
+    pub fn merge<K, V>(&mut self, key: K, value: V) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.batch.merge_cf(self.cf, key, value)
+    }
+
+    pub fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), Error> {
+        self.batch.delete_cf(self.cf, key)
+    }
+
+    /// Remove database entries in the column family from start key to end
+    /// key.
+    ///
+    /// Removes the database entries in the range ["begin_key", "end_key"), i.e.,
+    /// including "begin_key" and excluding "end_key". It is not an error if no
+    /// keys exist in the range ["begin_key", "end_key").
+    pub fn delete_range<K: AsRef<[u8]>>(&mut self, from: K, to: K) -> Result<(), Error> {
+        self.batch.delete_range_cf(self.cf, from, to)
+    }
+
+    /// Clear all updates buffered in this batch.
+    pub fn clear(&mut self) -> Result<(), Error> {
+        self.batch.clear()
+    }
+
+    /// Unwraps the underlying `WriteBatch`.
+    pub fn into_inner(self) -> WriteBatch {
+        self.batch
+    }
+}
+
+impl Deref for CfWriteBatch<'_> {
+    type Target = WriteBatch;
+
+    fn deref(&self) -> &WriteBatch {
+        &self.batch
+    }
+}
+
+/// Receives the operations of a `WriteBatch` from
+/// [`WriteBatch::iterate`](struct.WriteBatch.html#method.iterate).
+///
+/// The column families are told apart by their ids, see `ColumnFamily::id`.
+/// By default the operations of the default column family, whose id is 0,
+/// are passed on to `put` and `delete`, and the others are ignored.
+pub trait WriteBatchIterator {
+    /// Called with a put operation.
+    fn put(&mut self, key: &[u8], value: &[u8]);
+
+    /// Called with a delete operation.
+    fn delete(&mut self, key: &[u8]);
+
+    /// Called with a put operation on any column family.
+    fn put_cf(&mut self, column_family_id: u32, key: &[u8], value: &[u8]) {
+        if column_family_id == 0 {
+            self.put(key, value);
+        }
+    }
+
+    /// Called with a delete operation on any column family.
+    fn delete_cf(&mut self, column_family_id: u32, key: &[u8]) {
+        if column_family_id == 0 {
+            self.delete(key);
+        }
+    }
+
+    /// Called with a merge operation on any column family.
+    fn merge_cf(&mut self, _column_family_id: u32, _key: &[u8], _value: &[u8]) {}
+}
+
+struct IterateState<'a, H> {
+    handler: &'a mut H,
+    // the number of operations passed to the handler
+    handled: usize,
+}
+
+unsafe extern "C" fn writebatch_put_cf_callback<H: WriteBatchIterator>(
+    state: *mut c_void,
+    column_family_id: u32,
+    k: *const c_char,
+    klen: size_t,
+    v: *const c_char,
+    vlen: size_t,
+) {
+    unsafe {
+        let state = &mut *(state as *mut IterateState<H>);
+        let key = slice::from_raw_parts(k as *const u8, klen);
+        let value = slice::from_raw_parts(v as *const u8, vlen);
+        state.handler.put_cf(column_family_id, key, value);
+        state.handled += 1;
+    }
+}
+
+unsafe extern "C" fn writebatch_delete_cf_callback<H: WriteBatchIterator>(
+    state: *mut c_void,
+    column_family_id: u32,
+    k: *const c_char,
+    klen: size_t,
+) {
+    unsafe {
+        let state = &mut *(state as *mut IterateState<H>);
+        let key = slice::from_raw_parts(k as *const u8, klen);
+        state.handler.delete_cf(column_family_id, key);
+        state.handled += 1;
+    }
+}
+
+unsafe extern "C" fn writebatch_merge_cf_callback<H: WriteBatchIterator>(
+    state: *mut c_void,
+    column_family_id: u32,
+    k: *const c_char,
+    klen: size_t,
+    v: *const c_char,
+    vlen: size_t,
+) {
+    unsafe {
+        let state = &mut *(state as *mut IterateState<H>);
+        let key = slice::from_raw_parts(k as *const u8, klen);
+        let value = slice::from_raw_parts(v as *const u8, vlen);
+        state.handler.merge_cf(column_family_id, key, value);
+        state.handled += 1;
+    }
+}
+
 impl Default for WriteBatch {
     fn default() -> WriteBatch {
         WriteBatch {
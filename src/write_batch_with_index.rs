@@ -0,0 +1,348 @@
+// Copyright 2019 Tyler Neely
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+//
+
+use crate::ffi;
+
+use crate::{ColumnFamily, DBRawIterator, DBVector, Error, Options, ReadOptions, handle::Handle};
+
+use libc::{c_char, c_uchar, size_t};
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+
+/// A batch of write operations which, unlike `WriteBatch`, keeps an index
+/// of its keys, so that the staged writes can be read back before the
+/// batch is written, on their own or merged with the content of a DB.
+///
+/// ```
+/// use ckb_rocksdb::{prelude::*, WriteBatchWithIndex};
+/// # use ckb_rocksdb::TemporaryDBPath;
+///
+/// let path = "_path_for_rocksdb_storage_wbwi";
+/// # let path = TemporaryDBPath::new();
+/// # {
+/// let db = DB::open_default(&path).unwrap();
+/// db.put(b"k1", b"v1").unwrap();
+///
+/// let mut batch = WriteBatchWithIndex::default();
+/// batch.put(b"k2", b"v2").unwrap();
+///
+/// let value = batch.get_from_batch_and_db(&db, None, b"k1").unwrap();
+/// assert_eq!(&*value.unwrap(), b"v1");
+/// let value = batch.get_from_batch_and_db(&db, None, b"k2").unwrap();
+/// assert_eq!(&*value.unwrap(), b"v2");
+///
+/// db.write_wbwi(&batch).unwrap();
+/// # }
+/// ```
+pub struct WriteBatchWithIndex {
+    inner: *mut ffi::rocksdb_writebatch_wi_t,
+}
+
+impl WriteBatchWithIndex {
+    /// Creates an empty batch.
+    ///
+    /// With `overwrite_keys`, a later write of a key replaces the former
+    /// one in the index, which is what reading back the batch expects.
+    pub fn new(reserved_bytes: usize, overwrite_keys: bool) -> WriteBatchWithIndex {
+        WriteBatchWithIndex {
+            inner: unsafe {
+                ffi::rocksdb_writebatch_wi_create(reserved_bytes, overwrite_keys as c_uchar)
+            },
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        unsafe { ffi::rocksdb_writebatch_wi_count(self.inner) as usize }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Insert a value into the database under the given key.
+    pub fn put<K, V>(&mut self, key: K, value: V) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let key = key.as_ref();
+        let value = value.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_put(
+                self.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            );
+            Ok(())
+        }
+    }
+
+    pub fn put_cf<K, V>(&mut self, cf: &ColumnFamily, key: K, value: V) -> Result<(), Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let key = key.as_ref();
+        let value = value.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_put_cf(
+                self.inner,
+                cf.handle(),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+                value.as_ptr() as *const c_char,
+                value.len() as size_t,
+            );
+            Ok(())
+        }
+    }
+
+    /// Remove the database entry for key.
+    pub fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), Error> {
+        let key = key.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_delete(
+                self.inner,
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            );
+            Ok(())
+        }
+    }
+
+    pub fn delete_cf<K: AsRef<[u8]>>(&mut self, cf: &ColumnFamily, key: K) -> Result<(), Error> {
+        let key = key.as_ref();
+
+        unsafe {
+            ffi::rocksdb_writebatch_wi_delete_cf(
+                self.inner,
+                cf.handle(),
+                key.as_ptr() as *const c_char,
+                key.len() as size_t,
+            );
+            Ok(())
+        }
+    }
+
+    /// Clear all updates buffered in this batch.
+    pub fn clear(&mut self) -> Result<(), Error> {
+        unsafe {
+            ffi::rocksdb_writebatch_wi_clear(self.inner);
+        }
+        Ok(())
+    }
+
+    /// Reads a key from the batch only, ignoring the DB. `options` provides
+    /// the merge operator used to resolve merges staged in the batch.
+    ///
+    /// Returns `None` both when the key is not in the batch and when the
+    /// batch deletes it.
+    pub fn get_from_batch<K: AsRef<[u8]>>(
+        &self,
+        options: &Options,
+        key: K,
+    ) -> Result<Option<DBVector>, Error> {
+        self.get_from_batch_full(None, options, key)
+    }
+
+    /// Reads a key of a column family from the batch only, ignoring the DB.
+    pub fn get_from_batch_cf<K: AsRef<[u8]>>(
+        &self,
+        cf: &ColumnFamily,
+        options: &Options,
+        key: K,
+    ) -> Result<Option<DBVector>, Error> {
+        self.get_from_batch_full(Some(cf), options, key)
+    }
+
+    fn get_from_batch_full<K: AsRef<[u8]>>(
+        &self,
+        cf: Option<&ColumnFamily>,
+        options: &Options,
+        key: K,
+    ) -> Result<Option<DBVector>, Error> {
+        let key = key.as_ref();
+        let mut val_len: size_t = 0;
+
+        unsafe {
+            let val = match cf {
+                Some(cf) => ffi_try!(ffi::rocksdb_writebatch_wi_get_from_batch_cf(
+                    self.inner,
+                    options.inner,
+                    cf.handle(),
+                    key.as_ptr() as *const c_char,
+                    key.len() as size_t,
+                    &mut val_len,
+                )),
+                None => ffi_try!(ffi::rocksdb_writebatch_wi_get_from_batch(
+                    self.inner,
+                    options.inner,
+                    key.as_ptr() as *const c_char,
+                    key.len() as size_t,
+                    &mut val_len,
+                )),
+            };
+
+            if val.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(DBVector::from_c(val as *mut u8, val_len)))
+            }
+        }
+    }
+
+    /// Wraps an iterator over a DB into an iterator over the merged view of
+    /// the DB and of the default column family writes staged in this batch,
+    /// as if the batch was already written.
+    ///
+    /// The batch must not be modified while the returned iterator is used.
+    pub fn iterator_with_base<'a>(&'a self, base: DBRawIterator<'a>) -> DBRawIterator<'a> {
+        let mut base = ManuallyDrop::new(base);
+        // the merged iterator takes ownership of the base iterator
+        DBRawIterator {
+            inner: unsafe {
+                ffi::rocksdb_writebatch_wi_create_iterator_with_base(self.inner, base.inner)
+            },
+            db: PhantomData,
+            readopts: base.readopts.take(),
+        }
+    }
+
+    /// Wraps an iterator over a column family into an iterator over the
+    /// merged view of the column family and of the writes to it staged in
+    /// this batch, as if the batch was already written.
+    ///
+    /// The batch must not be modified while the returned iterator is used.
+    pub fn iterator_with_base_cf<'a>(
+        &'a self,
+        base: DBRawIterator<'a>,
+        cf: &ColumnFamily,
+    ) -> DBRawIterator<'a> {
+        let mut base = ManuallyDrop::new(base);
+        // the merged iterator takes ownership of the base iterator
+        DBRawIterator {
+            inner: unsafe {
+                ffi::rocksdb_writebatch_wi_create_iterator_with_base_cf(
+                    self.inner,
+                    base.inner,
+                    cf.handle(),
+                )
+            },
+            db: PhantomData,
+            readopts: base.readopts.take(),
+        }
+    }
+
+    /// Reads a key as if the batch was already written to `db`: the staged
+    /// writes take precedence over the content of the DB.
+    pub fn get_from_batch_and_db<T, K>(
+        &self,
+        db: &T,
+        readopts: Option<&ReadOptions>,
+        key: K,
+    ) -> Result<Option<DBVector>, Error>
+    where
+        T: Handle<ffi::rocksdb_t>,
+        K: AsRef<[u8]>,
+    {
+        self.get_from_batch_and_db_full(db, None, readopts, key)
+    }
+
+    /// Reads a key of a column family as if the batch was already written
+    /// to `db`.
+    pub fn get_from_batch_and_db_cf<T, K>(
+        &self,
+        db: &T,
+        cf: &ColumnFamily,
+        readopts: Option<&ReadOptions>,
+        key: K,
+    ) -> Result<Option<DBVector>, Error>
+    where
+        T: Handle<ffi::rocksdb_t>,
+        K: AsRef<[u8]>,
+    {
+        self.get_from_batch_and_db_full(db, Some(cf), readopts, key)
+    }
+
+    fn get_from_batch_and_db_full<T, K>(
+        &self,
+        db: &T,
+        cf: Option<&ColumnFamily>,
+        readopts: Option<&ReadOptions>,
+        key: K,
+    ) -> Result<Option<DBVector>, Error>
+    where
+        T: Handle<ffi::rocksdb_t>,
+        K: AsRef<[u8]>,
+    {
+        let mut default_readopts = None;
+
+        let ro_handle = ReadOptions::input_or_default(readopts, &mut default_readopts)?;
+
+        let key = key.as_ref();
+        let mut val_len: size_t = 0;
+
+        unsafe {
+            let val = match cf {
+                Some(cf) => ffi_try!(ffi::rocksdb_writebatch_wi_get_from_batch_and_db_cf(
+                    self.inner,
+                    db.handle(),
+                    ro_handle,
+                    cf.handle(),
+                    key.as_ptr() as *const c_char,
+                    key.len() as size_t,
+                    &mut val_len,
+                )),
+                None => ffi_try!(ffi::rocksdb_writebatch_wi_get_from_batch_and_db(
+                    self.inner,
+                    db.handle(),
+                    ro_handle,
+                    key.as_ptr() as *const c_char,
+                    key.len() as size_t,
+                    &mut val_len,
+                )),
+            };
+
+            if val.is_null() {
+                Ok(None)
+            } else {
+                Ok(Some(DBVector::from_c(val as *mut u8, val_len)))
+            }
+        }
+    }
+}
+
+impl Default for WriteBatchWithIndex {
+    fn default() -> WriteBatchWithIndex {
+        WriteBatchWithIndex::new(0, true)
+    }
+}
+
+impl Drop for WriteBatchWithIndex {
+    fn drop(&mut self) {
+        unsafe { ffi::rocksdb_writebatch_wi_destroy(self.inner) }
+    }
+}
+
+impl Handle<ffi::rocksdb_writebatch_wi_t> for WriteBatchWithIndex {
+    fn handle(&self) -> *mut ffi::rocksdb_writebatch_wi_t {
+        self.inner
+    }
+}
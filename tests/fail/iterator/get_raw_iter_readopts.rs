@@ -0,0 +1,13 @@
+extern crate ckb_rocksdb as rocksdb;
+
+use rocksdb::ops::{Iterate, Open};
+use rocksdb::{DB, ReadOptions};
+
+fn main() {
+    let db = DB::open_default("foo").unwrap();
+    let _iter = {
+        let mut readopts = ReadOptions::default();
+        readopts.set_iterate_upper_bound(b"k2");
+        db.get_raw_iter(&readopts)
+    };
+}
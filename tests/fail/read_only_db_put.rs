@@ -0,0 +1,9 @@
+extern crate ckb_rocksdb as rocksdb;
+
+use rocksdb::ReadOnlyDB;
+use rocksdb::prelude::*;
+
+fn main() {
+    let db = ReadOnlyDB::open_default("foo").unwrap();
+    db.put(b"k1", b"v1").unwrap();
+}
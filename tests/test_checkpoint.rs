@@ -95,6 +95,33 @@ pub fn test_multi_checkpoints() {
     assert_eq!(*cp.get(b"k6").unwrap().unwrap(), *b"v6");
 }
 
+#[test]
+pub fn test_checkpoint_is_independent() {
+    let db_path = TemporaryDBPath::new();
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    let db = DB::open(&opts, &db_path).unwrap();
+    db.put(b"k1", b"v1").unwrap();
+
+    let cp1 = Checkpoint::new(&db).unwrap();
+    let tmp_path = TemporaryDBPath::new();
+    let cp1_path = tmp_path.join("cp1");
+    cp1.create_checkpoint(&cp1_path).unwrap();
+    // the target directory must not exist yet
+    assert!(cp1.create_checkpoint(&cp1_path).is_err());
+
+    // both databases are open at the same time and diverge
+    let cp = DB::open_default(&cp1_path).unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    cp.put(b"k3", b"v3").unwrap();
+
+    assert_eq!(*cp.get(b"k1").unwrap().unwrap(), *b"v1");
+    assert!(cp.get(b"k2").unwrap().is_none());
+    assert_eq!(*db.get(b"k2").unwrap().unwrap(), *b"v2");
+    assert!(db.get(b"k3").unwrap().is_none());
+}
+
 // FIXME: windows
 #[cfg(not(target_os = "windows"))]
 #[test]
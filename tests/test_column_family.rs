@@ -237,6 +237,43 @@ fn test_column_family_with_options() {
     }
 }
 
+#[test]
+#[cfg(all(feature = "lz4", feature = "zstd"))]
+fn test_column_family_descriptors_with_distinct_compression() {
+    use crate::rocksdb::DBCompressionType;
+
+    let n = TemporaryDBPath::new();
+    let descriptors = || {
+        let mut lz4_opts = Options::default();
+        lz4_opts.set_compression_type(DBCompressionType::Lz4);
+        let mut zstd_opts = Options::default();
+        zstd_opts.set_compression_type(DBCompressionType::Zstd);
+        vec![
+            ColumnFamilyDescriptor::new("lz4", lz4_opts),
+            ColumnFamilyDescriptor::new("zstd", zstd_opts),
+        ]
+    };
+
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf_descriptors(&opts, &n, descriptors()).unwrap();
+
+        for name in ["lz4", "zstd"] {
+            let cf = db.cf_handle(name).unwrap();
+            db.put_cf(cf, b"k1", name).unwrap();
+            db.flush_cf(cf).unwrap();
+        }
+    }
+
+    let db = DB::open_cf_descriptors(&Options::default(), &n, descriptors()).unwrap();
+    for name in ["lz4", "zstd"] {
+        let cf = db.cf_handle(name).unwrap();
+        assert_eq!(&*db.get_cf(cf, b"k1").unwrap().unwrap(), name.as_bytes());
+    }
+}
+
 #[test]
 fn test_create_duplicate_column_family() {
     let n = TemporaryDBPath::new();
@@ -253,3 +290,44 @@ fn test_create_duplicate_column_family() {
         assert!(db.create_cf("cf1", &opts).is_err());
     }
 }
+
+#[test]
+#[cfg(feature = "lz4")]
+fn test_create_cf_at_runtime_with_options() {
+    use crate::rocksdb::DBCompressionType;
+
+    let n = TemporaryDBPath::new();
+    let mut db = DB::open_default(&n).unwrap();
+
+    let mut cf_opts = Options::default();
+    cf_opts.set_compression_type(DBCompressionType::Lz4);
+    db.create_cf("lz4", &cf_opts).unwrap();
+
+    let cf = db.cf_handle("lz4").unwrap();
+    db.put_cf(cf, b"k1", b"v1").unwrap();
+    db.flush_cf(cf).unwrap();
+    assert_eq!(&*db.get_cf(cf, b"k1").unwrap().unwrap(), b"v1");
+
+    db.drop_cf("lz4").unwrap();
+    assert!(db.cf_handle("lz4").is_none());
+}
+
+#[test]
+fn test_list_cf_of_closed_db() {
+    let n = TemporaryDBPath::new();
+
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        DB::open_cf(&opts, &n, ["default", "a", "b"]).unwrap();
+    }
+
+    let mut cfs = DB::list_cf(&Options::default(), &n).unwrap();
+    cfs.sort();
+    assert_eq!(cfs, ["a", "b", "default"]);
+
+    // opening without listing every column family fails
+    assert!(DB::open_cf(&Options::default(), &n, ["a"]).is_err());
+    assert!(DB::open_cf(&Options::default(), &n, cfs).is_ok());
+}
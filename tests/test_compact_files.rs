@@ -0,0 +1,318 @@
+extern crate ckb_rocksdb as rocksdb;
+
+use crate::rocksdb::{
+    BottommostLevelCompaction, CompactOptions, CompactionOptions, TemporaryDBPath, prelude::*,
+};
+
+#[test]
+fn compact_files_to_level() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_disable_auto_compactions(true);
+
+    let db = DB::open(&opts, &path).unwrap();
+
+    db.put(b"k1", b"v1").unwrap();
+    db.flush().unwrap();
+    db.put(b"k2", b"v2").unwrap();
+    db.flush().unwrap();
+
+    let files = db.live_files().unwrap();
+    assert_eq!(files.len(), 2);
+    assert!(files.iter().all(|f| f.level == 0));
+
+    let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+    db.compact_files(&names, 1, &CompactionOptions::default())
+        .unwrap();
+
+    let files = db.live_files().unwrap();
+    assert!(!files.is_empty());
+    assert!(files.iter().all(|f| f.level == 1));
+    assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+    assert_eq!(&*db.get(b"k2").unwrap().unwrap(), b"v2");
+}
+
+#[test]
+fn compact_files_cf_to_level() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    opts.set_disable_auto_compactions(true);
+
+    let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+
+    db.put_cf(cf1, b"k1", b"v1").unwrap();
+    db.flush_cf(cf1).unwrap();
+
+    let files = db.live_files().unwrap();
+    assert!(files.iter().all(|f| f.column_family_name == "cf1"));
+    let names: Vec<&str> = files.iter().map(|f| f.name.as_str()).collect();
+    assert_eq!(names.len(), 1);
+
+    let mut compact_opts = CompactionOptions::default();
+    compact_opts.set_max_subcompactions(1);
+    db.compact_files_cf(cf1, &names, 3, &compact_opts).unwrap();
+
+    let files = db.live_files().unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].column_family_name, "cf1");
+    assert_eq!(files[0].level, 3);
+    assert_eq!(&*db.get_cf(cf1, b"k1").unwrap().unwrap(), b"v1");
+    assert!(db.get(b"k1").unwrap().is_none());
+}
+
+#[test]
+fn live_files_at_level() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    opts.set_disable_auto_compactions(true);
+
+    let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+
+    for i in 0..3u8 {
+        db.put_cf(cf1, [i], [i]).unwrap();
+        db.flush_cf(cf1).unwrap();
+    }
+    db.put(b"k1", b"v1").unwrap();
+    db.flush().unwrap();
+
+    let files = db.live_files_at_level_cf(cf1, 0).unwrap();
+    assert_eq!(files.len(), 3);
+    assert!(files.iter().all(|f| f.level == 0));
+    assert!(files.iter().all(|f| f.column_family_name == "cf1"));
+    assert!(db.live_files_at_level_cf(cf1, 1).unwrap().is_empty());
+
+    let files = db.live_files_at_level(0).unwrap();
+    assert_eq!(files.len(), 1);
+    assert_eq!(files[0].column_family_name, "default");
+
+    assert_eq!(&*db.get_cf(cf1, [1]).unwrap().unwrap(), &[1]);
+    assert!(db.get([1]).unwrap().is_none());
+}
+
+#[test]
+fn flush_range_cf() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+
+    let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+    let cf = db.cf_handle("cf1").unwrap();
+
+    for i in 0..1000u32 {
+        db.put_cf(cf, i.to_be_bytes(), i.to_be_bytes()).unwrap();
+    }
+    assert!(
+        db.live_files()
+            .unwrap()
+            .iter()
+            .all(|f| f.column_family_name != "cf1")
+    );
+
+    db.flush_range_cf(
+        cf,
+        Some(&100u32.to_be_bytes()[..]),
+        Some(&200u32.to_be_bytes()[..]),
+    )
+    .unwrap();
+
+    let files: Vec<_> = db
+        .live_files()
+        .unwrap()
+        .into_iter()
+        .filter(|f| f.column_family_name == "cf1")
+        .collect();
+    assert!(!files.is_empty());
+    assert!(files.iter().any(|f| {
+        f.start_key.as_deref() <= Some(&100u32.to_be_bytes()[..])
+            && f.end_key.as_deref() >= Some(&199u32.to_be_bytes()[..])
+    }));
+    assert_eq!(
+        db.property_int_value_cf(cf, "rocksdb.num-entries-active-mem-table")
+            .unwrap(),
+        Some(0)
+    );
+}
+
+#[test]
+fn compact_range_reclaims_deleted_keys() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    opts.set_disable_auto_compactions(true);
+
+    let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+    let cf = db.cf_handle("cf1").unwrap();
+
+    for i in 0..1000u32 {
+        db.put(i.to_be_bytes(), i.to_be_bytes()).unwrap();
+        db.put_cf(cf, i.to_be_bytes(), i.to_be_bytes()).unwrap();
+    }
+    db.flush().unwrap();
+    db.flush_range_cf(cf, None, None).unwrap();
+    for i in 0..900u32 {
+        db.delete(i.to_be_bytes()).unwrap();
+        db.delete_cf(cf, i.to_be_bytes()).unwrap();
+    }
+    db.flush().unwrap();
+    db.flush_range_cf(cf, None, None).unwrap();
+
+    let estimate = |cf| {
+        match cf {
+            Some(cf) => db.property_int_value_cf(cf, "rocksdb.estimate-num-keys"),
+            None => db.property_int_value("rocksdb.estimate-num-keys"),
+        }
+        .unwrap()
+        .unwrap()
+    };
+    let before = estimate(None);
+
+    db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    assert!(estimate(None) < before);
+    assert_eq!(estimate(None), 100);
+
+    db.compact_range_cf(cf, Some(&0u32.to_be_bytes()[..]), None);
+    assert_eq!(estimate(Some(cf)), 100);
+    assert!(db.get_cf(cf, 0u32.to_be_bytes()).unwrap().is_none());
+    assert!(db.get_cf(cf, 900u32.to_be_bytes()).unwrap().is_some());
+}
+
+#[test]
+fn compact_range_bottommost_level() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_disable_auto_compactions(true);
+    opts.set_num_levels(2);
+
+    let db = DB::open(&opts, &path).unwrap();
+
+    // two non overlapping files in the bottommost level
+    for range in [0..100u32, 100..200u32] {
+        for i in range {
+            db.put(i.to_be_bytes(), i.to_be_bytes()).unwrap();
+        }
+        db.flush().unwrap();
+        let names: Vec<String> = db
+            .live_files_at_level(0)
+            .unwrap()
+            .into_iter()
+            .map(|f| f.name)
+            .collect();
+        db.compact_files(&names, 1, &CompactionOptions::default())
+            .unwrap();
+    }
+    let files_at_level1 = || {
+        db.property_int_value("rocksdb.num-files-at-level1")
+            .unwrap()
+            .unwrap()
+    };
+    assert_eq!(files_at_level1(), 2);
+
+    let mut compact_opts = CompactOptions::default();
+    compact_opts.set_bottommost_level_compaction(BottommostLevelCompaction::Skip);
+    db.compact_range_opt(None::<&[u8]>, None::<&[u8]>, &compact_opts);
+    assert_eq!(files_at_level1(), 2);
+
+    compact_opts.set_bottommost_level_compaction(BottommostLevelCompaction::Force);
+    compact_opts.set_exclusive_manual_compaction(true);
+    db.compact_range_opt(None::<&[u8]>, None::<&[u8]>, &compact_opts);
+    assert_eq!(files_at_level1(), 1);
+    assert_eq!(
+        &*db.get(150u32.to_be_bytes()).unwrap().unwrap(),
+        &150u32.to_be_bytes()
+    );
+}
+
+#[test]
+fn delete_file_in_range_drops_whole_files() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    opts.set_disable_auto_compactions(true);
+    opts.set_num_levels(2);
+
+    let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+
+    // four non overlapping files in level 1, files in level 0 are never
+    // deleted by range
+    for start in (0..400u32).step_by(100) {
+        for i in start..start + 100 {
+            db.put_cf(cf1, i.to_be_bytes(), i.to_be_bytes()).unwrap();
+        }
+        db.flush_cf(cf1).unwrap();
+        let names: Vec<String> = db
+            .live_files_at_level_cf(cf1, 0)
+            .unwrap()
+            .into_iter()
+            .map(|f| f.name)
+            .collect();
+        db.compact_files_cf(cf1, &names, 1, &CompactionOptions::default())
+            .unwrap();
+    }
+    let files_at_level1 = || {
+        db.property_int_value_cf(cf1, "rocksdb.num-files-at-level1")
+            .unwrap()
+            .unwrap()
+    };
+    assert_eq!(files_at_level1(), 4);
+
+    db.delete_file_in_range_cf(cf1, 100u32.to_be_bytes(), 299u32.to_be_bytes())
+        .unwrap();
+    assert_eq!(files_at_level1(), 2);
+    assert!(db.get_cf(cf1, 150u32.to_be_bytes()).unwrap().is_none());
+    assert!(db.get_cf(cf1, 250u32.to_be_bytes()).unwrap().is_none());
+    assert!(db.get_cf(cf1, 50u32.to_be_bytes()).unwrap().is_some());
+    assert!(db.get_cf(cf1, 350u32.to_be_bytes()).unwrap().is_some());
+
+    // a range only partly covering a file keeps it
+    db.delete_file_in_range_cf(cf1, 0u32.to_be_bytes(), 49u32.to_be_bytes())
+        .unwrap();
+    assert_eq!(files_at_level1(), 2);
+}
+
+#[test]
+fn compact_range_cf_to_target_level() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    opts.set_disable_auto_compactions(true);
+    // otherwise the output goes to the last level anyway
+    opts.set_level_compaction_dynamic_level_bytes(false);
+
+    let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+    for i in 0..3u8 {
+        db.put_cf(cf1, [i], [i]).unwrap();
+        db.flush_cf(cf1).unwrap();
+    }
+    assert_eq!(db.live_files_at_level_cf(cf1, 0).unwrap().len(), 3);
+
+    let mut compact_opts = CompactOptions::default();
+    compact_opts.set_change_level(true);
+    compact_opts.set_target_level(6);
+    db.compact_range_cf_opt(cf1, None, None, &compact_opts);
+
+    let files: Vec<_> = db
+        .live_files()
+        .unwrap()
+        .into_iter()
+        .filter(|f| f.column_family_name == "cf1")
+        .collect();
+    assert!(!files.is_empty());
+    assert!(files.iter().all(|f| f.level == 6));
+    for i in 0..3u8 {
+        assert_eq!(&*db.get_cf(cf1, [i]).unwrap().unwrap(), &[i]);
+    }
+}
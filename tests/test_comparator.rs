@@ -0,0 +1,108 @@
+extern crate ckb_rocksdb as rocksdb;
+
+use crate::rocksdb::{ColumnFamilyDescriptor, TemporaryDBPath, prelude::*};
+use std::cmp::Ordering;
+
+const TS_SIZE: usize = 8;
+
+fn strip_ts(key: &[u8], has_ts: bool) -> &[u8] {
+    if has_ts {
+        &key[..key.len() - TS_SIZE]
+    } else {
+        key
+    }
+}
+
+fn compare_ts(a: &[u8], b: &[u8]) -> Ordering {
+    let a = u64::from_le_bytes(a.try_into().unwrap());
+    let b = u64::from_le_bytes(b.try_into().unwrap());
+    a.cmp(&b)
+}
+
+fn compare_without_ts(a: &[u8], a_has_ts: bool, b: &[u8], b_has_ts: bool) -> Ordering {
+    strip_ts(a, a_has_ts).cmp(strip_ts(b, b_has_ts))
+}
+
+// user keys ascending, then newer timestamps first
+fn compare(a: &[u8], b: &[u8]) -> Ordering {
+    compare_without_ts(a, true, b, true)
+        .then_with(|| compare_ts(&a[a.len() - TS_SIZE..], &b[b.len() - TS_SIZE..]).reverse())
+}
+
+#[test]
+fn test_comparator_with_ts() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_comparator_with_ts(
+            "test.u64ts",
+            TS_SIZE,
+            compare,
+            compare_ts,
+            compare_without_ts,
+        );
+        let db = DB::open(&opts, &path).unwrap();
+
+        db.put_with_ts(b"k1", 1u64.to_le_bytes(), b"v1").unwrap();
+        db.put_with_ts(b"k1", 2u64.to_le_bytes(), b"v2").unwrap();
+
+        let get_at = |ts: u64| {
+            let mut readopts = ReadOptions::default();
+            readopts.set_timestamp(ts.to_le_bytes());
+            db.get_opt(b"k1", &readopts).unwrap()
+        };
+        assert!(get_at(0).is_none());
+        assert_eq!(&*get_at(1).unwrap(), b"v1");
+        assert_eq!(&*get_at(2).unwrap(), b"v2");
+        assert_eq!(&*get_at(3).unwrap(), b"v2");
+
+        // versions survive a flush
+        db.flush().unwrap();
+        assert_eq!(&*get_at(1).unwrap(), b"v1");
+        assert_eq!(&*get_at(2).unwrap(), b"v2");
+    }
+}
+
+#[test]
+fn test_get_cf_with_ts() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut cf_opts = Options::default();
+        cf_opts.set_comparator_with_ts(
+            "test.u64ts",
+            TS_SIZE,
+            compare,
+            compare_ts,
+            compare_without_ts,
+        );
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf_descriptors(
+            &opts,
+            &path,
+            vec![ColumnFamilyDescriptor::new("cf1", cf_opts)],
+        )
+        .unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        db.put_cf_with_ts(cf1, b"k1", 5u64.to_le_bytes(), b"v5")
+            .unwrap();
+        db.put_cf_with_ts(cf1, b"k1", 9u64.to_le_bytes(), b"v9")
+            .unwrap();
+
+        let get_at = |ts: u64| {
+            let mut readopts = ReadOptions::default();
+            readopts.set_timestamp(ts.to_le_bytes());
+            db.get_cf_with_ts(cf1, b"k1", &readopts).unwrap()
+        };
+        assert!(get_at(4).is_none());
+        let (value, ts) = get_at(7).unwrap();
+        assert_eq!(&*value, b"v5");
+        assert_eq!(ts, 5u64.to_le_bytes());
+        let (value, ts) = get_at(u64::MAX).unwrap();
+        assert_eq!(&*value, b"v9");
+        assert_eq!(ts, 9u64.to_le_bytes());
+    }
+}
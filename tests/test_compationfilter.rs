@@ -46,3 +46,71 @@ fn compaction_filter_test() {
         assert_eq!(&*db.get(b"%k").unwrap().unwrap(), b"secret");
     }
 }
+
+#[test]
+fn compaction_filter_ttl_test() {
+    const NOW: u8 = 10;
+
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    // values are prefixed with the byte of their expiry time
+    opts.set_compaction_filter("ttl", |_level: u32, _key: &[u8], value: &[u8]| match value
+        .split_first()
+    {
+        Some((&expiry, _)) if expiry < NOW => CompactionDecision::Remove,
+        Some((&u8::MAX, payload)) => {
+            CompactionDecision::ChangeValue([&[NOW * 2][..], payload].concat())
+        }
+        _ => CompactionDecision::Keep,
+    });
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"expired1", [1, b'a']).unwrap();
+        db.put(b"expired2", [NOW - 1, b'b']).unwrap();
+        db.put(b"live", [NOW, b'c']).unwrap();
+        db.put(b"refresh", [u8::MAX, b'd', b'e']).unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        assert!(db.get(b"expired1").unwrap().is_none());
+        assert!(db.get(b"expired2").unwrap().is_none());
+        assert_eq!(&*db.get(b"live").unwrap().unwrap(), &[NOW, b'c']);
+        assert_eq!(
+            &*db.get(b"refresh").unwrap().unwrap(),
+            &[NOW * 2, b'd', b'e']
+        );
+    }
+}
+
+#[test]
+fn compaction_filter_skip_until_test() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    // everything from "k20" up to "k50" is garbage
+    opts.set_compaction_filter("skip", |_level: u32, key: &[u8], _value: &[u8]| {
+        if key == b"k20" {
+            CompactionDecision::RemoveAndSkipUntil(b"k50".to_vec())
+        } else {
+            CompactionDecision::Keep
+        }
+    });
+    {
+        let db = DB::open(&opts, &path).unwrap();
+        for i in 0..100 {
+            let key = format!("k{:02}", i);
+            db.put(&key, &key).unwrap();
+        }
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        for i in 0..100 {
+            let key = format!("k{:02}", i);
+            let value = db.get(&key).unwrap();
+            if (20..50).contains(&i) {
+                assert!(value.is_none(), "{} should be removed", key);
+            } else {
+                assert_eq!(&*value.unwrap(), key.as_bytes());
+            }
+        }
+    }
+}
@@ -15,7 +15,10 @@
 extern crate ckb_rocksdb as rocksdb;
 use libc::size_t;
 
-use crate::rocksdb::{IteratorMode, TemporaryDBPath, WriteBatch, prelude::*};
+use crate::rocksdb::{
+    DBCompressionType, FlushOptions, IteratorMode, OptimisticTransactionDB, TemporaryDBPath,
+    TransactionDB, WriteBatch, prelude::*,
+};
 
 #[test]
 fn test_db_vector() {
@@ -27,6 +30,84 @@ fn test_db_vector() {
     assert_eq!(&*v, &ctrl[..]);
 }
 
+#[test]
+fn test_db_vector_read() {
+    use flate2::{Compression, read::GzDecoder, write::GzEncoder};
+    use std::io::{Read, Write};
+
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+        let original = b"a serialized blob ".repeat(1000);
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&original).unwrap();
+        db.put(b"blob", encoder.finish().unwrap()).unwrap();
+
+        let value = db.get(b"blob").unwrap().unwrap();
+        let compressed_len = value.len();
+        let mut decoder = GzDecoder::new(value);
+        let mut decoded = Vec::new();
+        decoder.read_to_end(&mut decoded).unwrap();
+        assert_eq!(decoded, original);
+
+        // reading doesn't change the bytes seen as a slice
+        assert_eq!(decoder.into_inner().len(), compressed_len);
+    }
+}
+
+#[test]
+fn test_db_vector_ord_and_hash() {
+    use std::collections::{BTreeSet, HashSet};
+
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"b").unwrap();
+        db.put(b"k2", b"a").unwrap();
+        db.put(b"k3", b"b").unwrap();
+        db.put(b"k4", b"ab").unwrap();
+
+        let keys = [b"k1", b"k2", b"k3", b"k4"];
+        let values: BTreeSet<_> = keys.iter().map(|k| db.get(k).unwrap().unwrap()).collect();
+        let values: Vec<_> = values.iter().map(|v| v.to_vec()).collect();
+        assert_eq!(values, [&b"a"[..], b"ab", b"b"]);
+
+        let pinned: HashSet<_> = keys
+            .iter()
+            .map(|k| db.get_pinned(k).unwrap().unwrap())
+            .collect();
+        assert_eq!(pinned.len(), 3);
+        assert!(pinned.contains(&db.get_pinned(b"k4").unwrap().unwrap()));
+    }
+}
+
+#[test]
+#[cfg(feature = "serde1")]
+fn test_db_vector_serde() {
+    use crate::rocksdb::DBValue;
+
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"value12345").unwrap();
+
+        let value = db.get(b"k1").unwrap().unwrap();
+        let encoded = bincode::serialize(&value).unwrap();
+        assert_eq!(
+            encoded,
+            bincode::serialize(&b"value12345".to_vec()).unwrap()
+        );
+        let decoded: DBValue = bincode::deserialize(&encoded).unwrap();
+        assert_eq!(&*decoded, b"value12345");
+        assert_eq!(decoded, DBValue::from(value));
+        assert_eq!(bincode::serialize(&decoded).unwrap(), encoded);
+
+        let pinned = db.get_pinned(b"k1").unwrap().unwrap();
+        assert_eq!(bincode::serialize(&pinned).unwrap(), encoded);
+        assert_eq!(DBValue::from(pinned), decoded);
+    }
+}
+
 #[test]
 fn external() {
     let path = TemporaryDBPath::new();
@@ -157,6 +238,34 @@ fn snapshot_test() {
     }
 }
 
+#[test]
+fn read_options_with_snapshot_test() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let snap = db.snapshot();
+        let readopts = ReadOptions::default().with_snapshot(&snap);
+
+        db.put(b"k1", b"v2").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+
+        assert_eq!(
+            db.get_opt(b"k1", &readopts).unwrap().unwrap().as_ref(),
+            b"v1"
+        );
+        assert!(db.get_opt(b"k2", &readopts).unwrap().is_none());
+        assert_eq!(db.get(b"k1").unwrap().unwrap().as_ref(), b"v2");
+
+        // clones stay pinned to the snapshot
+        let mut cloned = readopts.clone();
+        cloned.fill_cache(false);
+        assert_eq!(db.get_opt(b"k1", &cloned).unwrap().unwrap().as_ref(), b"v1");
+        assert!(db.get_opt(b"k2", &cloned).unwrap().is_none());
+    }
+}
+
 #[test]
 fn set_option_test() {
     let path = TemporaryDBPath::new();
@@ -200,6 +309,125 @@ fn set_option_test() {
     }
 }
 
+#[test]
+fn set_db_option_test() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.set_db_options(&[("max_background_jobs", "8")]).unwrap();
+        // DB-wide options are not column family options and vice versa
+        assert!(db.set_options(&[("max_background_jobs", "8")]).is_err());
+        assert!(
+            db.set_db_options(&[("disable_auto_compactions", "true")])
+                .is_err()
+        );
+        // invalid names/values should result in an error
+        assert!(
+            db.set_db_options(&[("max_background_jobs", "INVALID_VALUE")])
+                .is_err()
+        );
+        assert!(
+            db.set_db_options(&[("INVALID_NAME", "INVALID_VALUE")])
+                .is_err()
+        );
+        db.set_db_options(&[
+            ("max_background_jobs", "4"),
+            ("stats_dump_period_sec", "60"),
+        ])
+        .unwrap();
+    }
+}
+
+#[test]
+fn flush_test() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+
+        let mut flushopts = FlushOptions::default();
+        flushopts.set_wait(true);
+        db.flush_opt(&flushopts).unwrap();
+        assert_eq!(
+            db.property_int_value("rocksdb.num-immutable-mem-table")
+                .unwrap(),
+            Some(0)
+        );
+        assert_eq!(db.live_files_at_level(0).unwrap().len(), 1);
+        assert!(db.live_files_at_level_cf(cf1, 0).unwrap().is_empty());
+
+        db.flush_cf_opt(cf1, &flushopts).unwrap();
+        assert_eq!(
+            db.property_int_value_cf(cf1, "rocksdb.num-immutable-mem-table")
+                .unwrap(),
+            Some(0)
+        );
+        assert_eq!(db.live_files_at_level_cf(cf1, 0).unwrap().len(), 1);
+
+        db.put_cf(cf1, b"k2", b"v2").unwrap();
+        db.flush_cf(cf1).unwrap();
+        assert_eq!(db.live_files_at_level_cf(cf1, 0).unwrap().len(), 2);
+    }
+}
+
+#[test]
+fn wait_memtable_flush_cf_test() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        for i in 0..10u8 {
+            db.put_cf(cf1, [i], [i]).unwrap();
+        }
+        db.wait_memtable_flush_cf(cf1).unwrap();
+
+        assert!(!db.mem_table_flush_pending_cf(cf1).unwrap());
+        assert!(!db.live_files_at_level_cf(cf1, 0).unwrap().is_empty());
+        assert_eq!(
+            db.property_int_value_cf(cf1, "rocksdb.num-entries-active-mem-table")
+                .unwrap(),
+            Some(0)
+        );
+    }
+}
+
+#[test]
+fn pause_compactions_cf_test() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_level_zero_file_num_compaction_trigger(2);
+        let db = DB::open(&opts, &path).unwrap();
+        let cf = db.cf_handle("default").unwrap();
+
+        db.pause_compactions_cf(cf).unwrap();
+        for i in 0..4u8 {
+            db.put([i], [i]).unwrap();
+            db.flush().unwrap();
+        }
+        // no background compaction merged the flushed files
+        assert_eq!(db.live_files_at_level_cf(cf, 0).unwrap().len(), 4);
+
+        db.resume_compactions_cf(cf).unwrap();
+        db.compact_range_cf(cf, None, None);
+        assert!(db.live_files_at_level_cf(cf, 0).unwrap().is_empty());
+        for i in 0..4u8 {
+            assert_eq!(&*db.get([i]).unwrap().unwrap(), &[i]);
+        }
+    }
+}
+
 #[test]
 fn set_option_cf_test() {
     let path = TemporaryDBPath::new();
@@ -332,6 +560,107 @@ fn multi_get() {
     }
 }
 
+#[test]
+fn multi_get_empty() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf0"]).unwrap();
+        let cf = db.cf_handle("cf0").unwrap();
+
+        assert!(db.multi_get(Vec::<&[u8]>::new()).is_empty());
+        assert!(
+            db.multi_get_cf(Vec::<(&ColumnFamily, &[u8])>::new())
+                .is_empty()
+        );
+        assert!(
+            db.batched_multi_get_cf(cf, Vec::<&[u8]>::new(), false)
+                .is_empty()
+        );
+
+        let snapshot = db.snapshot();
+        assert!(snapshot.multi_get(Vec::<&[u8]>::new()).is_empty());
+    }
+    {
+        let path = TemporaryDBPath::new();
+        let db = TransactionDB::open_default(&path).unwrap();
+        assert!(db.multi_get(Vec::<&[u8]>::new()).is_empty());
+
+        let txn = db.transaction_default();
+        assert!(txn.multi_get(Vec::<&[u8]>::new()).is_empty());
+    }
+    {
+        let path = TemporaryDBPath::new();
+        let db = OptimisticTransactionDB::open_default(&path).unwrap();
+        let txn = db.transaction_default();
+        assert!(txn.multi_get(Vec::<&[u8]>::new()).is_empty());
+    }
+}
+
+#[test]
+fn multi_get_parallel() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+        for i in (0..10_000u32).step_by(3) {
+            db.put(i.to_be_bytes(), i.to_le_bytes()).unwrap();
+        }
+        db.flush().unwrap();
+
+        let keys: Vec<_> = (0..10_000u32).map(u32::to_be_bytes).collect();
+        let serial = db.multi_get(&keys);
+        for num_threads in [0, 1, 4, 7] {
+            let parallel = db.multi_get_parallel(&keys, num_threads);
+            assert_eq!(parallel.len(), serial.len());
+            for (p, s) in parallel.iter().zip(&serial) {
+                assert_eq!(
+                    p.as_ref().unwrap().as_deref(),
+                    s.as_ref().unwrap().as_deref()
+                );
+            }
+        }
+        assert!(db.multi_get_parallel(&[] as &[&[u8]], 4).is_empty());
+    }
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn par_multi_get_cf() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf0", "cf1"]).unwrap();
+        let cf0 = db.cf_handle("cf0").unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        for i in (0..1000u32).step_by(3) {
+            db.put_cf(cf0, i.to_be_bytes(), i.to_le_bytes()).unwrap();
+            db.put_cf(cf1, i.to_be_bytes(), (i + 1).to_le_bytes())
+                .unwrap();
+        }
+
+        let keys_cf: Vec<_> = (0..1000u32)
+            .map(|i| (if i % 2 == 0 { cf0 } else { cf1 }, i.to_be_bytes()))
+            .collect();
+        let serial = db.multi_get_cf(keys_cf.iter().map(|(cf, k)| (*cf, k)));
+        let parallel = db.par_multi_get_cf(&keys_cf);
+        assert_eq!(parallel.len(), 1000);
+        for (p, s) in parallel.iter().zip(&serial) {
+            assert_eq!(
+                p.as_ref().unwrap().as_deref(),
+                s.as_ref().unwrap().as_deref()
+            );
+        }
+        assert!(
+            db.par_multi_get_cf(&[] as &[(&ColumnFamily, &[u8])])
+                .is_empty()
+        );
+    }
+}
+
 #[test]
 fn multi_get_cf() {
     let tmp = TemporaryDBPath::new();
@@ -392,3 +721,203 @@ fn batched_multi_get_cf() {
         assert_eq!(&(values[2].as_ref().unwrap())[0..2], b"v2");
     }
 }
+
+#[test]
+fn batched_multi_get() {
+    let path = TemporaryDBPath::new();
+
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+
+        let values = db
+            .batched_multi_get(vec![b"k0", b"k1", b"k2"], true) // sorted_input
+            .into_iter()
+            .map(Result::unwrap)
+            .map(|v| v.map(|v| v.to_vec()))
+            .collect::<Vec<_>>();
+        assert_eq!(3, values.len());
+        assert!(values[0].is_none());
+        assert_eq!(values[1].as_deref(), Some(&b"v1"[..]));
+        assert_eq!(values[2].as_deref(), Some(&b"v2"[..]));
+
+        assert!(db.batched_multi_get(Vec::<&[u8]>::new(), false).is_empty());
+    }
+}
+
+#[test]
+fn db_identity_is_stable() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_write_dbid_to_manifest(true);
+
+    let identity = {
+        let db = DB::open(&opts, &path).unwrap();
+        db.get_db_identity().unwrap()
+    };
+    assert!(!identity.is_empty());
+
+    let db = DB::open(&opts, &path).unwrap();
+    assert_eq!(db.get_db_identity().unwrap(), identity);
+}
+
+#[test]
+fn key_may_exist_test() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+        db.delete(b"k1").unwrap();
+        db.flush().unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        db.put(b"k2", b"v2").unwrap();
+
+        assert!(!db.key_may_exist(b"k1", None));
+        assert!(db.key_may_exist(b"k2", None));
+        assert!(db.key_may_exist(b"k2", Some(&ReadOptions::default())));
+
+        db.put_cf(cf1, b"k3", b"v3").unwrap();
+        assert!(db.key_may_exist_cf(cf1, b"k3", None));
+        assert!(!db.key_may_exist_cf(cf1, b"k2", None));
+    }
+}
+
+#[test]
+fn approximate_sizes_test() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        opts.set_compression_type(DBCompressionType::None);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        for i in 0..10_000u32 {
+            let key = format!("key{:05}", i);
+            db.put(&key, [i as u8; 100]).unwrap();
+            db.put_cf(cf1, &key, [i as u8; 100]).unwrap();
+        }
+        db.flush().unwrap();
+        db.flush_cf(cf1).unwrap();
+
+        let sizes = db
+            .get_approximate_sizes(&[
+                (b"key00000", b"key99999"),
+                (b"key00000", b"key05000"),
+                (b"zzz00000", b"zzz99999"),
+            ])
+            .unwrap();
+        assert_eq!(sizes.len(), 3);
+        let (full, half, empty) = (sizes[0], sizes[1], sizes[2]);
+        assert!(full > 0);
+        assert!(half > full / 4 && half < full * 3 / 4);
+        assert_eq!(empty, 0);
+
+        let sizes = db
+            .get_approximate_sizes_cf(cf1, &[("key00000", "key99999")])
+            .unwrap();
+        assert_eq!(sizes.len(), 1);
+        assert!(sizes[0] > 0);
+    }
+}
+
+#[test]
+fn get_updates_since_test() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k0", b"v0").unwrap();
+
+        let seq = db.latest_sequence_number();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        db.delete(b"k1").unwrap();
+        assert_eq!(db.latest_sequence_number(), seq + 3);
+
+        let updates = db
+            .get_updates_since(seq + 1)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(updates.len(), 3);
+        for (i, (batch_seq, batch)) in updates.iter().enumerate() {
+            assert_eq!(*batch_seq, seq + 1 + i as u64);
+            assert_eq!(batch.len(), 1);
+        }
+    }
+}
+
+#[test]
+fn snapshot_sequence_number() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k0", b"v0").unwrap();
+
+        let snapshot = db.snapshot();
+        assert_eq!(snapshot.sequence_number(), db.latest_sequence_number());
+        db.put(b"k1", b"v1").unwrap();
+        assert!(db.latest_sequence_number() > snapshot.sequence_number());
+    }
+}
+
+#[test]
+fn snapshot_and_updates_since_test() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k0", b"v0").unwrap();
+
+        let (snapshot, seq) = db.snapshot_and_updates_since();
+        assert_eq!(seq, db.latest_sequence_number());
+        db.put(b"k1", b"v1").unwrap();
+        db.delete(b"k0").unwrap();
+
+        assert_eq!(&*snapshot.get(b"k0").unwrap().unwrap(), b"v0");
+        assert!(snapshot.get(b"k1").unwrap().is_none());
+
+        let updates = db
+            .get_updates_since(seq + 1)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(updates.len(), 2);
+        assert_eq!(updates[0].0, seq + 1);
+        assert_eq!(updates[1].0, seq + 2);
+    }
+}
+
+#[test]
+fn test_write_options() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+
+        let mut ingest = WriteOptions::default();
+        ingest.set_disable_wal(true);
+        db.put_opt(b"k1", b"v1", &ingest).unwrap();
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+
+        let mut metadata = WriteOptions::default();
+        metadata.set_sync(true);
+        db.put_opt(b"k2", b"v2", &metadata).unwrap();
+
+        let mut background = WriteOptions::default();
+        background.set_no_slowdown(true);
+        background.set_low_pri(true);
+        let mut batch = WriteBatch::default();
+        batch.put(b"k3", b"v3").unwrap();
+        db.write_opt(&batch, &background.clone()).unwrap();
+
+        assert_eq!(&*db.get(b"k2").unwrap().unwrap(), b"v2");
+        assert_eq!(&*db.get(b"k3").unwrap().unwrap(), b"v3");
+    }
+}
@@ -0,0 +1,70 @@
+extern crate ckb_rocksdb as rocksdb;
+
+use crate::rocksdb::{CompactionJobInfo, EventListener, FlushJobInfo, TemporaryDBPath, prelude::*};
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Default)]
+struct Counter {
+    flushes: AtomicUsize,
+    compactions: AtomicUsize,
+    flushed_cfs: Mutex<Vec<String>>,
+}
+
+struct Listener(Arc<Counter>);
+
+impl EventListener for Listener {
+    fn on_flush_completed(&self, info: &FlushJobInfo) {
+        self.0
+            .flushed_cfs
+            .lock()
+            .unwrap()
+            .push(info.cf_name.clone());
+        self.0.flushes.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn on_compaction_completed(&self, info: &CompactionJobInfo) {
+        if info.status.is_ok() {
+            self.0.compactions.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+}
+
+// the notification may land just after the call that triggered it returns
+fn wait_for(counter: &AtomicUsize, n: usize) {
+    let start = Instant::now();
+    while counter.load(Ordering::SeqCst) < n && start.elapsed() < Duration::from_secs(10) {
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(counter.load(Ordering::SeqCst), n);
+}
+
+#[test]
+fn test_event_listener() {
+    let path = TemporaryDBPath::new();
+    let counter = Arc::new(Counter::default());
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_disable_auto_compactions(true);
+        opts.add_event_listener(Listener(counter.clone()));
+        let db = DB::open(&opts, &path).unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        wait_for(&counter.flushes, 1);
+        assert_eq!(*counter.flushed_cfs.lock().unwrap(), ["default"]);
+
+        db.put(b"k2", b"v2").unwrap();
+        db.flush().unwrap();
+        wait_for(&counter.flushes, 2);
+
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        wait_for(&counter.compactions, 1);
+    }
+    // the options and the database dropped their references to the listener
+    assert_eq!(Arc::strong_count(&counter), 1);
+}
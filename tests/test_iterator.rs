@@ -14,7 +14,14 @@
 //
 extern crate ckb_rocksdb as rocksdb;
 
-use crate::rocksdb::{Direction, IteratorMode, MemtableFactory, TemporaryDBPath, prelude::*};
+use crate::rocksdb::{
+    Direction, IteratorMode, MemtableFactory, OptimisticTransactionDB, OwnedDBIterator,
+    TablePropertiesCollector, TablePropertiesCollectorFactory, TemporaryDBPath, prelude::*,
+};
+
+use std::ffi::{CStr, CString};
+use std::sync::{Arc, Mutex};
+use std::thread;
 
 fn cba(input: &[u8]) -> Box<[u8]> {
     input.to_vec().into_boxed_slice()
@@ -296,6 +303,252 @@ fn test_full_iterator() {
     }
 }
 
+#[test]
+fn test_iterator_bounds() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+        for key in [b"k1", b"k2", b"k3", b"k4"] {
+            db.put(key, key).unwrap();
+        }
+
+        let mut readopts = ReadOptions::default();
+        readopts.set_iterate_upper_bound(b"k3");
+        let keys: Vec<_> = db
+            .iterator_opt(IteratorMode::Start, &readopts)
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, vec![cba(b"k1"), cba(b"k2")]);
+
+        let mut readopts = ReadOptions::default();
+        readopts.set_iterate_lower_bound(b"k2");
+        let keys: Vec<_> = db
+            .iterator_opt(IteratorMode::End, &readopts)
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, vec![cba(b"k4"), cba(b"k3"), cba(b"k2")]);
+
+        readopts.set_iterate_upper_bound(b"k4");
+        // the bounds are kept alive by the cloned options
+        let readopts = readopts.clone();
+        let keys: Vec<_> = db
+            .iterator_opt(IteratorMode::Start, &readopts)
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, vec![cba(b"k2"), cba(b"k3")]);
+    }
+}
+
+#[test]
+fn test_iterator_cf_opt() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        for key in [b"k1", b"k2", b"k3", b"k4", b"k5"] {
+            db.put_cf(cf1, key, key).unwrap();
+        }
+
+        let mut readopts = ReadOptions::default();
+        readopts.fill_cache(false);
+        readopts.set_iterate_lower_bound(b"k2");
+        readopts.set_iterate_upper_bound(b"k5");
+        let iter = db
+            .iterator_cf_opt(cf1, IteratorMode::Start, &readopts)
+            .unwrap();
+        let expected = vec![
+            (cba(b"k2"), cba(b"k2")),
+            (cba(b"k3"), cba(b"k3")),
+            (cba(b"k4"), cba(b"k4")),
+        ];
+        assert_eq!(iter.collect::<Vec<_>>(), expected);
+
+        let keys: Vec<_> = db
+            .iterator_cf_opt(cf1, IteratorMode::End, &readopts)
+            .unwrap()
+            .map(|(k, _)| k)
+            .collect();
+        assert_eq!(keys, vec![cba(b"k4"), cba(b"k3"), cba(b"k2")]);
+    }
+}
+
+struct MaxValueCollector {
+    max: u8,
+    name: CString,
+}
+
+impl TablePropertiesCollector for MaxValueCollector {
+    fn add(&mut self, _key: &[u8], value: &[u8]) {
+        self.max = self.max.max(value[0]);
+    }
+
+    fn finish(&mut self) -> Vec<(Vec<u8>, Vec<u8>)> {
+        vec![(b"max_value".to_vec(), vec![self.max])]
+    }
+
+    fn name(&self) -> &CStr {
+        &self.name
+    }
+}
+
+struct MaxValueCollectorFactory(CString);
+
+impl TablePropertiesCollectorFactory for MaxValueCollectorFactory {
+    type Collector = MaxValueCollector;
+
+    fn create(&self, _column_family_id: u32) -> MaxValueCollector {
+        MaxValueCollector {
+            max: 0,
+            name: CString::new("MaxValueCollector").unwrap(),
+        }
+    }
+
+    fn name(&self) -> &CStr {
+        &self.0
+    }
+}
+
+#[test]
+fn test_iterator_table_filter() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_disable_auto_compactions(true);
+    opts.add_table_properties_collector_factory(MaxValueCollectorFactory(
+        CString::new("MaxValueCollectorFactory").unwrap(),
+    ));
+    let db = DB::open(&opts, &path).unwrap();
+
+    for i in 0..3u8 {
+        db.put([b'a', i], [i]).unwrap();
+    }
+    db.flush().unwrap();
+    for i in 5..10u8 {
+        db.put([b'b', i], [i]).unwrap();
+    }
+    db.flush().unwrap();
+
+    // the filter runs inside a callback from RocksDB, where a panic would
+    // abort, so what it sees is checked once the scan is done
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let mut readopts = ReadOptions::default();
+    let seen_by_filter = seen.clone();
+    readopts.set_table_filter(move |props| {
+        let max_value = props
+            .user_collected_property("max_value")
+            .map(<[u8]>::to_vec);
+        let keep = max_value.as_ref().is_some_and(|max| max[0] >= 5);
+        seen_by_filter.lock().unwrap().push((
+            props.column_family_name().to_vec(),
+            max_value,
+            props.user_collected_property("missing").is_some(),
+        ));
+        keep
+    });
+    let keys: Vec<_> = db
+        .iterator_opt(IteratorMode::Start, &readopts)
+        .map(|(k, _)| k)
+        .collect();
+    assert_eq!(keys.len(), 5);
+    assert!(keys.iter().all(|k| k[0] == b'b'));
+
+    let mut seen = seen.lock().unwrap().clone();
+    seen.sort();
+    seen.dedup();
+    assert_eq!(
+        seen,
+        vec![
+            (b"default".to_vec(), Some(vec![2]), false),
+            (b"default".to_vec(), Some(vec![9]), false),
+        ]
+    );
+
+    // point lookups do not consult the table filter
+    let readopts = readopts.clone();
+    assert_eq!(&*db.get_opt([b'a', 0], &readopts).unwrap().unwrap(), &[0]);
+}
+
+#[test]
+fn test_owned_iterator_send() {
+    let n = TemporaryDBPath::new();
+    {
+        let db = Arc::new(DB::open_default(&n).unwrap());
+        for i in 0..100u8 {
+            db.put([i], [i]).unwrap();
+        }
+
+        let iter = OwnedDBIterator::new(db.clone(), IteratorMode::Start);
+        let handle = thread::spawn(move || iter.collect::<Vec<_>>());
+        let entries = handle.join().unwrap();
+        assert_eq!(entries.len(), 100);
+        for (i, (k, v)) in entries.iter().enumerate() {
+            assert_eq!(&**k, &[i as u8]);
+            assert_eq!(&**v, &[i as u8]);
+        }
+
+        // the iterator keeps the database alive on its own
+        let iter = OwnedDBIterator::new(db, IteratorMode::End);
+        let handle = thread::spawn(move || iter.map(|(k, _)| k).next());
+        assert_eq!(&*handle.join().unwrap().unwrap(), &[99]);
+    }
+    {
+        let db = Arc::new(OptimisticTransactionDB::open_default(&n).unwrap());
+        let iter = OwnedDBIterator::new(db, IteratorMode::Start);
+        let count = thread::spawn(move || iter.count()).join().unwrap();
+        assert_eq!(count, 100);
+    }
+}
+
+#[test]
+fn test_range_digest() {
+    let n1 = TemporaryDBPath::new();
+    let n2 = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db1 = DB::open_cf(&opts, &n1, ["cf1"]).unwrap();
+        let db2 = DB::open_cf(&opts, &n2, ["cf1"]).unwrap();
+
+        for i in 0..100u8 {
+            db1.put([i], [i; 10]).unwrap();
+            db2.put([i], [i; 10]).unwrap();
+        }
+        // same data laid out differently on disk
+        db1.flush().unwrap();
+
+        let digest1 = db1.range_digest([10], [50]).unwrap();
+        assert_eq!(digest1, db2.range_digest([10], [50]).unwrap());
+        assert_ne!(digest1, db1.range_digest([10], [51]).unwrap());
+
+        // writes outside of the range do not matter
+        db2.put([60], b"diverged").unwrap();
+        assert_eq!(digest1, db2.range_digest([10], [50]).unwrap());
+
+        db2.put([20], b"diverged").unwrap();
+        assert_ne!(digest1, db2.range_digest([10], [50]).unwrap());
+
+        let cf1 = db1.cf_handle("cf1").unwrap();
+        let cf2 = db2.cf_handle("cf1").unwrap();
+        db1.put_cf(cf1, b"k1", b"v1").unwrap();
+        db2.put_cf(cf2, b"k1", b"v1").unwrap();
+        assert_eq!(
+            db1.range_digest_cf(cf1, b"k0", b"k9").unwrap(),
+            db2.range_digest_cf(cf2, b"k0", b"k9").unwrap()
+        );
+        // moving a byte from the key to the value changes the digest
+        db2.delete_cf(cf2, b"k1").unwrap();
+        db2.put_cf(cf2, b"k", b"1v1").unwrap();
+        assert_ne!(
+            db1.range_digest_cf(cf1, b"k", b"k9").unwrap(),
+            db2.range_digest_cf(cf2, b"k", b"k9").unwrap()
+        );
+    }
+}
+
 // FIXME: windows
 #[cfg(not(target_os = "windows"))]
 #[test]
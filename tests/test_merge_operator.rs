@@ -14,7 +14,10 @@
 
 extern crate ckb_rocksdb as rocksdb;
 
-use ckb_rocksdb::ops::{CompactRange, Delete, Get, Merge, Open, Put};
+use ckb_rocksdb::ops::{
+    CompactRange, Delete, FlushRangeCF, Get, GetColumnFamilys, GetMergeOperands,
+    GetMergeOperandsCF, Merge, MergeCF, Open, OpenCF, Put,
+};
 use ckb_rocksdb::{DB, DBCompactionStyle, MergeOperands, Options, TemporaryDBPath};
 use rocksdb::merge_operator::MergeFn;
 
@@ -76,6 +79,36 @@ fn merge_test() {
     assert!(db.get(b"k1").unwrap().is_none());
 }
 
+#[test]
+fn get_merge_operands_test() {
+    let db_path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    opts.set_merge_operator_associative("test operator", test_provided_merge);
+
+    let db = DB::open_cf(&opts, &db_path, ["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+
+    db.merge_cf(cf1, b"k1", b"a").unwrap();
+    db.merge_cf(cf1, b"k1", b"b").unwrap();
+    db.flush_range_cf(cf1, None, None).unwrap();
+    db.merge_cf(cf1, b"k1", b"cd").unwrap();
+
+    let operands = db.get_merge_operands_cf(cf1, b"k1").unwrap();
+    let operands: Vec<&[u8]> = operands.iter().map(|v| v.as_ref()).collect();
+    assert_eq!(operands, vec![&b"a"[..], b"b", b"cd"]);
+
+    db.put(b"k1", b"x").unwrap();
+    db.merge(b"k1", b"y").unwrap();
+    let operands = db.get_merge_operands(b"k1").unwrap();
+    let operands: Vec<&[u8]> = operands.iter().map(|v| v.as_ref()).collect();
+    assert_eq!(operands, vec![&b"x"[..], b"y"]);
+    assert_eq!(db.get(b"k1").unwrap().unwrap().as_ref(), b"xy".as_ref());
+
+    assert!(db.get_merge_operands(b"missing").unwrap().is_empty());
+}
+
 unsafe fn to_slice<T: Sized>(p: &T) -> &[u8] {
     unsafe {
         ::std::slice::from_raw_parts((p as *const T) as *const u8, ::std::mem::size_of::<T>())
@@ -409,3 +442,112 @@ fn test_merge_state() {
     }
     assert!(DB::destroy(&opts, path).is_ok());
 }
+
+fn decode_u64(v: &[u8]) -> Option<u64> {
+    Some(u64::from_le_bytes(v.try_into().ok()?))
+}
+
+fn add_full_merge(
+    _new_key: &[u8],
+    existing_val: Option<&[u8]>,
+    operands: &mut MergeOperands,
+) -> Option<Vec<u8>> {
+    let mut sum = existing_val.map_or(Some(0), decode_u64)?;
+    for op in operands {
+        sum += decode_u64(op)?;
+    }
+    Some(sum.to_le_bytes().to_vec())
+}
+
+#[test]
+fn partial_merge_test() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let db_path = TemporaryDBPath::new();
+    let partial_merges = Arc::new(AtomicUsize::new(0));
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    let counter = partial_merges.clone();
+    opts.set_merge_operator(
+        "add operator",
+        add_full_merge,
+        move |_key: &[u8], _existing: Option<&[u8]>, operands: &mut MergeOperands| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            let mut sum = 0u64;
+            for op in operands {
+                let n = decode_u64(op)?;
+                // refuse large operands, leaving them to the full merge
+                if n >= 1000 {
+                    return None;
+                }
+                sum += n;
+            }
+            Some(sum.to_le_bytes().to_vec())
+        },
+    );
+
+    let db = DB::open(&opts, &db_path).unwrap();
+    for i in 1..=100u64 {
+        db.merge(b"k1", i.to_le_bytes()).unwrap();
+        db.merge(b"k2", (i * 1000).to_le_bytes()).unwrap();
+    }
+    db.flush().unwrap();
+    db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+    assert!(partial_merges.load(Ordering::SeqCst) > 0);
+    assert_eq!(decode_u64(&db.get(b"k1").unwrap().unwrap()), Some(5050));
+    assert_eq!(
+        decode_u64(&db.get(b"k2").unwrap().unwrap()),
+        Some(5_050_000)
+    );
+}
+
+#[allow(clippy::unnecessary_wraps)]
+fn last_write_wins_merge(
+    _new_key: &[u8],
+    existing_val: Option<&[u8]>,
+    operands: &mut MergeOperands,
+) -> Option<Vec<u8>> {
+    if operands.is_empty() {
+        return existing_val.map(<[u8]>::to_vec);
+    }
+    operands.get(operands.len() - 1).map(<[u8]>::to_vec)
+}
+
+#[test]
+fn merge_operands_index_test() {
+    let db_path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.set_merge_operator_associative("last write wins", last_write_wins_merge);
+
+    let db = DB::open(&opts, &db_path).unwrap();
+    db.put(b"k1", b"a").unwrap();
+    db.merge(b"k1", b"b").unwrap();
+    db.merge(b"k1", b"c").unwrap();
+    db.merge(b"k1", b"d").unwrap();
+    assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"d");
+
+    db.flush().unwrap();
+    db.merge(b"k1", b"e").unwrap();
+    db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"e");
+
+    // len and get don't depend on the iterator position
+    let mut opts = Options::default();
+    opts.set_merge_operator_associative(
+        "checked",
+        |_key: &[u8], _existing: Option<&[u8]>, operands: &mut MergeOperands| {
+            let first = operands.next()?;
+            (operands.len() == 2 && operands.get(0) == Some(first) && operands.get(2).is_none())
+                .then(|| operands.get(1).unwrap().to_vec())
+        },
+    );
+    let db_path = TemporaryDBPath::new();
+    opts.create_if_missing(true);
+    let db = DB::open(&opts, &db_path).unwrap();
+    db.merge(b"k2", b"x").unwrap();
+    db.merge(b"k2", b"y").unwrap();
+    assert_eq!(&*db.get(b"k2").unwrap().unwrap(), b"y");
+}
@@ -109,6 +109,96 @@ pub fn test_optimistic_transaction() {
     }
 }
 
+#[test]
+pub fn test_optimistic_transaction_conflict_keys() {
+    let n = TemporaryDBPath::new();
+    {
+        let db = OptimisticTransactionDB::open_default(&n).unwrap();
+
+        let trans1 = db.transaction_default();
+        let trans2 = db.transaction_default();
+
+        trans1.put(b"k1", b"v1").unwrap();
+        trans1.put(b"k2", b"v2").unwrap();
+        trans2.put(b"k2", b"v5").unwrap();
+
+        trans2.commit().unwrap();
+
+        let err = trans1.commit().unwrap_err();
+        assert_eq!(err.conflict_keys(), vec![b"k2".to_vec()]);
+    }
+}
+
+#[test]
+pub fn test_optimistic_transaction_conflict_keys_since_snapshot() {
+    let n = TemporaryDBPath::new();
+    {
+        let db = OptimisticTransactionDB::open_default(&n).unwrap();
+
+        let write_options = WriteOptions::default();
+        let mut transaction_options = OptimisticTransactionOptions::new();
+        transaction_options.set_snapshot(true);
+        let trans1 = db.transaction(&write_options, &transaction_options);
+
+        // written after the snapshot of trans1, before trans1 writes it
+        db.put(b"k1", b"v0").unwrap();
+        trans1.put(b"k1", b"v1").unwrap();
+        trans1.put(b"k2", b"v2").unwrap();
+
+        let err = trans1.commit().unwrap_err();
+        assert_eq!(err.conflict_keys(), vec![b"k1".to_vec()]);
+    }
+}
+
+#[test]
+pub fn test_optimistic_transaction_conflict_keys_since_tracked() {
+    let n = TemporaryDBPath::new();
+    {
+        let db = OptimisticTransactionDB::open_default(&n).unwrap();
+
+        let trans1 = db.transaction_default();
+
+        // written after trans1 began, but before trans1 first touches it
+        db.put(b"k1", b"v0").unwrap();
+        trans1.put(b"k1", b"v1").unwrap();
+        trans1.put(b"k2", b"v1").unwrap();
+        db.put(b"k2", b"v0").unwrap();
+
+        let err = trans1.commit().unwrap_err();
+        assert_eq!(err.conflict_keys(), vec![b"k2".to_vec()]);
+    }
+}
+
+#[test]
+pub fn test_optimistic_transaction_conflict_keys_after_rollback() {
+    let n = TemporaryDBPath::new();
+    {
+        let db = OptimisticTransactionDB::open_default(&n).unwrap();
+
+        let trans1 = db.transaction_default();
+        trans1.put(b"k1", b"v1").unwrap();
+        trans1.set_savepoint();
+        trans1.put(b"k2", b"v1").unwrap();
+        trans1.rollback_to_savepoint().unwrap();
+        trans1.put(b"k3", b"v1").unwrap();
+
+        let trans2 = db.transaction_default();
+        trans2.put(b"k2", b"v2").unwrap();
+        trans2.rollback().unwrap();
+        trans2.put(b"k4", b"v2").unwrap();
+
+        for key in [b"k1", b"k2", b"k3", b"k4"] {
+            db.put(key, b"v0").unwrap();
+        }
+
+        // the rolled back k2 is tracked by neither transaction
+        let err = trans1.commit().unwrap_err();
+        assert_eq!(err.conflict_keys(), vec![b"k1".to_vec(), b"k3".to_vec()]);
+        let err = trans2.commit().unwrap_err();
+        assert_eq!(err.conflict_keys(), vec![b"k4".to_vec()]);
+    }
+}
+
 #[test]
 pub fn test_optimistic_transaction_rollback_savepoint() {
     let path = TemporaryDBPath::new();
@@ -561,3 +651,25 @@ fn multi_get_cf() {
         assert_eq!(values[2], Some(b"v2".to_vec()));
     }
 }
+
+#[test]
+pub fn test_optimistic_transaction_snapshot_sequence_number() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = OptimisticTransactionDB::open_default(&path).unwrap();
+        db.put(b"k0", b"v0").unwrap();
+        let before = db.snapshot().sequence_number();
+
+        let write_options = WriteOptions::default();
+        let mut transaction_options = OptimisticTransactionOptions::new();
+        let trans1 = db.transaction(&write_options, &transaction_options);
+        assert_eq!(trans1.snapshot().sequence_number(), None);
+
+        transaction_options.set_snapshot(true);
+        let trans2 = db.transaction(&write_options, &transaction_options);
+        assert_eq!(trans2.snapshot().sequence_number(), Some(before));
+
+        db.put(b"k1", b"v1").unwrap();
+        assert!(db.snapshot().sequence_number() > before);
+    }
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "perf")]
+
+extern crate ckb_rocksdb as rocksdb;
+
+use crate::rocksdb::{
+    TemporaryDBPath,
+    perf::{PerfContext, PerfLevel, set_perf_level},
+    prelude::*,
+};
+
+#[test]
+fn test_perf_context() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        set_perf_level(PerfLevel::EnableTime);
+        let mut ctx = PerfContext::new();
+        ctx.reset();
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+
+        let report = ctx.report(true);
+        assert!(!report.is_empty());
+        assert!(report.contains("get_from_memtable_count"));
+
+        ctx.reset();
+        set_perf_level(PerfLevel::Disable);
+        db.get(b"k1").unwrap();
+        assert!(!ctx.report(true).contains("get_from_memtable_count"));
+    }
+}
@@ -22,3 +22,72 @@ fn test_pinnable_slice() {
 
     assert_eq!(b"12345", &pinnable_slice[5..10]);
 }
+
+#[test]
+fn test_pinnable_slice_read() {
+    use std::io::Read;
+
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"value12345").unwrap();
+
+        let mut pinnable_slice = db.get_pinned(b"k1").unwrap().unwrap();
+        let mut buf = [0u8; 4];
+        assert_eq!(pinnable_slice.read(&mut buf).unwrap(), 4);
+        assert_eq!(&buf, b"valu");
+        let mut rest = Vec::new();
+        pinnable_slice.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b"e12345");
+        assert_eq!(pinnable_slice.read(&mut buf).unwrap(), 0);
+        assert_eq!(&*pinnable_slice, b"value12345");
+    }
+}
+
+#[test]
+fn test_snapshot_pinnable_slice() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+
+        let snap = db.snapshot();
+        db.put(b"k1", b"v2").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        db.put_cf(cf1, b"k1", b"v2").unwrap();
+
+        assert_eq!(&*snap.get_pinned(b"k1").unwrap().unwrap(), b"v1");
+        assert!(snap.get_pinned(b"k2").unwrap().is_none());
+        assert_eq!(&*snap.get_pinned_cf(cf1, b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(&*db.get_pinned(b"k1").unwrap().unwrap(), b"v2");
+    }
+}
+
+#[test]
+fn test_try_get_cf() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+
+        assert_eq!(&*db.try_get_cf(cf1, b"k1").unwrap().unwrap(), b"v1");
+        assert!(db.try_get_cf(cf1, b"k2").unwrap().is_none());
+
+        let snap = db.snapshot();
+        db.put_cf(cf1, b"k1", b"v3").unwrap();
+        assert_eq!(&*snap.try_get_cf(cf1, b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(&*db.try_get_cf(cf1, b"k1").unwrap().unwrap(), b"v3");
+    }
+}
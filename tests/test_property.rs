@@ -14,7 +14,11 @@
 //
 extern crate ckb_rocksdb as rocksdb;
 
-use crate::rocksdb::{TemporaryDBPath, prelude::*};
+use crate::rocksdb::{DBCompressionType, TemporaryDBPath, prelude::*};
+
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
 
 #[test]
 fn property_test() {
@@ -69,3 +73,126 @@ fn property_int_cf_test() {
         assert!(total_keys == Some(0));
     }
 }
+
+#[test]
+fn background_work_gauges() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_disable_auto_compactions(true);
+        opts.set_compression_type(DBCompressionType::None);
+        let db = DB::open(&opts, &n).unwrap();
+
+        for round in 0..8u32 {
+            for i in 0..5_000u32 {
+                let key = (i * 8 + round).to_be_bytes();
+                db.put(key, [round as u8; 128]).unwrap();
+            }
+            db.flush().unwrap();
+        }
+    }
+    {
+        // about 5MB to rewrite at 2MB/s keeps the compaction running for a
+        // couple of seconds
+        let mut opts = Options::default();
+        opts.set_disable_auto_compactions(true);
+        opts.set_compression_type(DBCompressionType::None);
+        opts.set_ratelimiter(2 * 1024 * 1024, 100 * 1000, 10);
+        let db = Arc::new(DB::open(&opts, &n).unwrap());
+        let cf = db.cf_handle("default").unwrap();
+
+        assert_eq!(db.num_running_compactions().unwrap(), Some(0));
+        assert_eq!(db.num_running_flushes().unwrap(), Some(0));
+        assert!(!db.mem_table_flush_pending().unwrap());
+        assert!(!db.mem_table_flush_pending_cf(cf).unwrap());
+
+        let compacting = db.clone();
+        let handle = thread::spawn(move || {
+            compacting.compact_range(None::<&[u8]>, None::<&[u8]>);
+        });
+
+        let deadline = Instant::now() + Duration::from_secs(30);
+        let mut seen_running = false;
+        while !handle.is_finished() && Instant::now() < deadline {
+            if db.num_running_compactions().unwrap() > Some(0) {
+                seen_running = true;
+                break;
+            }
+            thread::sleep(Duration::from_millis(1));
+        }
+        handle.join().unwrap();
+
+        assert!(seen_running);
+        assert_eq!(db.num_running_compactions().unwrap(), Some(0));
+    }
+}
+
+#[test]
+fn snapshot_gauges() {
+    let n = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&n).unwrap();
+        assert_eq!(db.num_snapshots().unwrap(), Some(0));
+        assert_eq!(db.oldest_snapshot_time().unwrap(), Some(0));
+
+        let snapshot = db.snapshot();
+        assert_eq!(db.num_snapshots().unwrap(), Some(1));
+        assert!(db.oldest_snapshot_time().unwrap().unwrap() > 0);
+
+        drop(snapshot);
+        assert_eq!(db.num_snapshots().unwrap(), Some(0));
+        assert_eq!(db.oldest_snapshot_time().unwrap(), Some(0));
+    }
+}
+
+#[test]
+fn property_all_test() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &n, ["cf1"]).unwrap();
+        for i in 0..100u32 {
+            db.put(i.to_be_bytes(), b"value").unwrap();
+        }
+
+        let properties = db.property_all().unwrap();
+        assert!(properties["rocksdb.stats"].contains("Stats"));
+        assert!(properties.contains_key("rocksdb.cfstats-no-file-histogram"));
+        let num_keys: u64 = properties["rocksdb.estimate-num-keys"].parse().unwrap();
+        assert_eq!(num_keys, 100);
+        // only available with FIFO compaction
+        assert!(!properties.contains_key("rocksdb.estimate-oldest-key-time"));
+    }
+}
+
+#[test]
+fn typed_property_test() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &n, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        for i in 0..100u32 {
+            db.put(i.to_be_bytes(), b"value").unwrap();
+        }
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+
+        assert!(db.estimate_num_keys().unwrap().unwrap() > 0);
+        assert!(db.cur_size_all_mem_tables().unwrap().unwrap() > 0);
+        assert_eq!(db.total_sst_files_size().unwrap(), Some(0));
+        assert_eq!(db.num_running_compactions().unwrap(), Some(0));
+        assert_eq!(db.estimate_num_keys_cf(cf1).unwrap(), Some(1));
+
+        db.flush().unwrap();
+        assert!(db.total_sst_files_size().unwrap().unwrap() > 0);
+        assert!(db.estimate_live_data_size().unwrap().unwrap() > 0);
+        assert_eq!(db.total_sst_files_size_cf(cf1).unwrap(), Some(0));
+        assert_eq!(db.estimate_live_data_size_cf(cf1).unwrap(), Some(0));
+        assert!(db.cur_size_all_mem_tables_cf(cf1).unwrap().unwrap() > 0);
+    }
+}
@@ -150,6 +150,118 @@ pub fn test_seek_for_prev() {
     }
 }
 
+#[test]
+pub fn test_seek_for_prev_edges() {
+    let n = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k3", b"v3").unwrap();
+        db.put(b"k5", b"v5").unwrap();
+
+        let mut iter = db.raw_iterator();
+
+        iter.seek_for_prev(b"k4");
+        assert!(iter.valid());
+        assert_eq!(iter.key(), Some(b"k3".as_ref()));
+        assert_eq!(iter.value(), Some(b"v3".as_ref()));
+
+        // an exact match is its own predecessor
+        iter.seek_for_prev(b"k5");
+        assert_eq!(iter.key(), Some(b"k5".as_ref()));
+
+        iter.seek_for_prev(b"k9");
+        assert_eq!(iter.key(), Some(b"k5".as_ref()));
+
+        // stepping backward from the landing point
+        iter.seek_for_prev(b"k4");
+        iter.prev();
+        assert_eq!(iter.key(), Some(b"k1".as_ref()));
+
+        // nothing is <= the target
+        iter.seek_for_prev(b"k0");
+        assert!(!iter.valid());
+        assert_eq!(iter.key(), None);
+        assert_eq!(iter.value(), None);
+    }
+}
+
+#[test]
+pub fn test_status_after_exhaustion() {
+    let n = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k2", b"v2").unwrap();
+
+        let mut iter = db.raw_iterator();
+        iter.seek_to_first();
+        assert!(iter.status().is_ok());
+        while iter.valid() {
+            iter.next();
+        }
+        // reaching the end is not an error
+        assert!(iter.status().is_ok());
+
+        iter.seek(b"k9");
+        assert!(!iter.valid());
+        assert!(iter.status().is_ok());
+    }
+}
+
+#[test]
+pub fn test_tailing() {
+    let n = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let mut opts = ReadOptions::default();
+        opts.set_tailing(true);
+        let mut iter = db.get_raw_iter(&opts);
+        iter.seek_to_first();
+        assert_eq!(iter.key(), Some(b"k1".as_ref()));
+
+        db.put(b"k2", b"v2").unwrap();
+
+        iter.next();
+        assert!(iter.valid());
+        assert_eq!(iter.key(), Some(b"k2".as_ref()));
+        assert_eq!(iter.value(), Some(b"v2".as_ref()));
+
+        iter.next();
+        assert!(!iter.valid());
+        db.put(b"k3", b"v3").unwrap();
+        // an exhausted tailing iterator picks new keys up when seeked again
+        iter.seek(b"k3");
+        assert_eq!(iter.key(), Some(b"k3".as_ref()));
+    }
+}
+
+#[test]
+pub fn test_refresh() {
+    let n = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let mut iter = db.raw_iterator();
+        db.put(b"k2", b"v2").unwrap();
+
+        iter.seek(b"k2");
+        assert!(!iter.valid());
+
+        iter.refresh().unwrap();
+        iter.seek(b"k2");
+        assert!(iter.valid());
+        assert_eq!(iter.key(), Some(b"k2".as_ref()));
+        assert_eq!(iter.value(), Some(b"v2".as_ref()));
+
+        iter.seek_to_first();
+        assert_eq!(iter.key(), Some(b"k1".as_ref()));
+    }
+}
+
 #[test]
 fn iterator_test_past_end() {
     use crate::rocksdb::IteratorMode;
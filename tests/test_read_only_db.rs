@@ -32,3 +32,38 @@ fn open_existing_db_in_read_only() {
         assert!(r.unwrap().unwrap().to_utf8().unwrap() == "v1111");
     }
 }
+
+#[test]
+fn open_cf_for_read_only() {
+    let path = TemporaryDBPath::new();
+
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        db.put(b"k0", b"v0").unwrap();
+        db.put_cf(db.cf_handle("cf1").unwrap(), b"k1", b"v1")
+            .unwrap();
+    }
+
+    {
+        let opts = Options::default();
+        let db = ReadOnlyDB::open_for_read_only(&opts, &path, false).unwrap();
+        assert_eq!(&*db.get(b"k0").unwrap().unwrap(), b"v0");
+    }
+
+    {
+        let opts = Options::default();
+        let db = ReadOnlyDB::open_cf_for_read_only(&opts, &path, ["cf1"], false).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        assert_eq!(&*db.get_cf(cf1, b"k1").unwrap().unwrap(), b"v1");
+    }
+}
+
+#[cfg(not(target_os = "windows"))]
+#[test]
+fn read_only_db_put() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/fail/read_only_db_put.rs");
+}
@@ -14,7 +14,14 @@
 //
 extern crate ckb_rocksdb as rocksdb;
 
-use crate::rocksdb::{TemporaryDBPath, prelude::*};
+use crate::rocksdb::{
+    BlockBasedOptions, Cache, DBCompressionType, Env, IteratorMode, SstFileManager,
+    TemporaryDBPath, Ticker, prelude::*,
+};
+
+use std::fs;
+use std::thread;
+use std::time::{Duration, Instant};
 
 #[test]
 fn test_set_num_levels() {
@@ -37,3 +44,558 @@ fn test_increase_parallelism() {
         let _db = DB::open(&opts, &n).unwrap();
     }
 }
+
+#[test]
+fn test_set_memtable_huge_page_size() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        // Falls back to malloc when no huge pages are reserved, so this
+        // works on hosts without huge-page support as well.
+        opts.set_memtable_huge_page_size(2 * 1024 * 1024);
+        let db = DB::open(&opts, &n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+}
+
+#[test]
+fn test_set_manifest_preallocation_size() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_manifest_preallocation_size(64 * 1024);
+        let db = DB::open(&opts, &n).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+
+    let has_manifest = std::fs::read_dir(&n)
+        .unwrap()
+        .filter_map(Result::ok)
+        .any(|entry| entry.file_name().to_string_lossy().starts_with("MANIFEST-"));
+    assert!(has_manifest);
+}
+
+#[test]
+fn test_strict_capacity_limit_cache() {
+    let n = TemporaryDBPath::new();
+    let cache = Cache::new_lru_cache_with_strict_capacity_limit(1024);
+    {
+        let mut block_opts = BlockBasedOptions::default();
+        block_opts.set_block_cache(&cache);
+        block_opts.set_block_size(4 * 1024);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_block_based_table_factory(&block_opts);
+
+        let db = DB::open(&opts, &n).unwrap();
+        for i in 0..1000u32 {
+            db.put(i.to_be_bytes(), [0u8; 128]).unwrap();
+        }
+        db.flush().unwrap();
+
+        for i in 0..1000u32 {
+            assert_eq!(&*db.get(i.to_be_bytes()).unwrap().unwrap(), &[0u8; 128][..]);
+        }
+        assert!(cache.get_usage() <= 1024);
+    }
+    cache.disown_data();
+}
+
+#[test]
+fn test_set_target_file_size_base() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compression_type(DBCompressionType::None);
+        opts.set_target_file_size_base(16 * 1024);
+        opts.set_target_file_size_multiplier(1);
+        let db = DB::open(&opts, &n).unwrap();
+
+        for i in 0..256u32 {
+            db.put(i.to_be_bytes(), [i as u8; 1024]).unwrap();
+        }
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        let files = db.live_files().unwrap();
+        assert!(files.len() > 1);
+        assert!(files.iter().all(|f| f.level > 0));
+    }
+}
+
+#[test]
+fn test_set_two_write_queues() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_two_write_queues(true);
+        let db = DB::open(&opts, &n).unwrap();
+
+        thread::scope(|s| {
+            for t in 0..4u32 {
+                let db = &db;
+                s.spawn(move || {
+                    for i in 0..1000u32 {
+                        db.put([t.to_be_bytes(), i.to_be_bytes()].concat(), i.to_be_bytes())
+                            .unwrap();
+                    }
+                });
+            }
+        });
+
+        for t in 0..4u32 {
+            for i in 0..1000u32 {
+                let key = [t.to_be_bytes(), i.to_be_bytes()].concat();
+                assert_eq!(&*db.get(key).unwrap().unwrap(), &i.to_be_bytes());
+            }
+        }
+    }
+}
+
+#[test]
+fn test_set_max_file_opening_threads() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_disable_auto_compactions(true);
+        let db = DB::open(&opts, &n).unwrap();
+        for i in 0..32u32 {
+            db.put(i.to_be_bytes(), i.to_be_bytes()).unwrap();
+            db.flush().unwrap();
+        }
+        assert_eq!(db.live_files().unwrap().len(), 32);
+    }
+    {
+        let mut opts = Options::default();
+        opts.set_max_open_files(-1);
+        opts.set_max_file_opening_threads(32);
+        let db = DB::open(&opts, &n).unwrap();
+        for i in 0..32u32 {
+            assert_eq!(
+                &*db.get(i.to_be_bytes()).unwrap().unwrap(),
+                &i.to_be_bytes()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_set_stats_dump_period_sec() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_stats_dump_period_sec(1);
+        opts.set_stats_persist_period_sec(1);
+        let db = DB::open(&opts, &n).unwrap();
+        for i in 0..100u32 {
+            db.put(i.to_be_bytes(), i.to_be_bytes()).unwrap();
+        }
+
+        // the periodic task scheduler runs the dump once the period elapsed
+        let log = n.as_ref().join("LOG");
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let mut dumped = false;
+        while !dumped && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(100));
+            dumped = fs::read_to_string(&log)
+                .unwrap()
+                .contains("------- DUMPING STATS -------");
+        }
+        assert!(dumped);
+    }
+}
+
+#[test]
+fn test_set_sst_file_manager() {
+    let n = TemporaryDBPath::new();
+    {
+        let manager = SstFileManager::new(&Env::default_env().unwrap());
+        manager.set_delete_rate_bytes_per_second(1024 * 1024);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_sst_file_manager(&manager);
+        let db = DB::open(&opts, &n).unwrap();
+        assert_eq!(manager.get_total_size(), 0);
+
+        for i in 0..1_000u32 {
+            db.put(i.to_be_bytes(), [0; 100]).unwrap();
+        }
+        db.flush().unwrap();
+        let flushed = manager.get_total_size();
+        assert!(flushed > 0);
+
+        for i in 1_000..2_000u32 {
+            db.put(i.to_be_bytes(), [0; 100]).unwrap();
+        }
+        db.flush().unwrap();
+        assert!(manager.get_total_size() > flushed);
+
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+        assert!(manager.get_total_size() > 0);
+        assert_eq!(db.get(1_500u32.to_be_bytes()).unwrap().unwrap().len(), 100);
+    }
+}
+
+#[test]
+fn test_set_avoid_unnecessary_blocking_io() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_avoid_unnecessary_blocking_io(true);
+        let db = DB::open(&opts, &n).unwrap();
+
+        for round in 0..50u32 {
+            db.put(round.to_be_bytes(), round.to_be_bytes()).unwrap();
+            if round % 10 == 0 {
+                db.flush().unwrap();
+            }
+            // iterators pinning flushed memtables are released in the background
+            let iter = db.iterator(IteratorMode::Start);
+            assert_eq!(iter.count(), round as usize + 1);
+        }
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        for round in 0..50u32 {
+            assert_eq!(
+                &*db.get(round.to_be_bytes()).unwrap().unwrap(),
+                &round.to_be_bytes()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_set_preserve_internal_time_seconds() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_preserve_internal_time_seconds(3600);
+        let db = DB::open(&opts, &n).unwrap();
+
+        for i in 0..100u32 {
+            db.put(i.to_be_bytes(), i.to_le_bytes()).unwrap();
+        }
+        db.flush().unwrap();
+        db.delete(7u32.to_be_bytes()).unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        assert!(db.get(7u32.to_be_bytes()).unwrap().is_none());
+        for i in (0..100u32).filter(|i| *i != 7) {
+            assert_eq!(
+                &*db.get(i.to_be_bytes()).unwrap().unwrap(),
+                &i.to_le_bytes()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_statistics() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.enable_statistics();
+        let db = DB::open(&opts, &n).unwrap();
+
+        for i in 0..10u32 {
+            db.put(i.to_be_bytes(), i.to_be_bytes()).unwrap();
+        }
+        db.flush().unwrap();
+        for i in 0..20u32 {
+            let _ = db.get(i.to_be_bytes()).unwrap();
+        }
+
+        let stats = opts.get_statistics().unwrap();
+        assert!(!stats.is_empty());
+        assert!(stats.contains(Ticker::BlockCacheHit.name()));
+        assert_eq!(opts.get_ticker_count(Ticker::NumberKeysWritten), 10);
+        assert_eq!(opts.get_ticker_count(Ticker::NumberKeysRead), 20);
+        let cache_lookups = opts.get_ticker_count(Ticker::BlockCacheHit)
+            + opts.get_ticker_count(Ticker::BlockCacheMiss);
+        assert!(cache_lookups > 0);
+    }
+}
+
+#[test]
+fn test_set_allow_data_in_errors() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_allow_data_in_errors(true);
+        let db = DB::open(&opts, &n).unwrap();
+
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+        db.put(b"k2", b"v2").unwrap();
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(&*db.get(b"k2").unwrap().unwrap(), b"v2");
+    }
+}
+
+#[test]
+fn test_shared_lru_block_cache() {
+    let n = TemporaryDBPath::new();
+    let cache = Cache::new_lru_cache(16 * 1024 * 1024);
+    {
+        let mut block_opts = BlockBasedOptions::default();
+        block_opts.set_block_cache(&cache);
+        block_opts.set_block_size(4 * 1024);
+        block_opts.set_cache_index_and_filter_blocks(true);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        opts.set_block_based_table_factory(&block_opts);
+
+        let db = DB::open_cf(&opts, &n, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        for i in 0..1000u32 {
+            db.put(i.to_be_bytes(), [1u8; 128]).unwrap();
+            db.put_cf(cf1, i.to_be_bytes(), [2u8; 128]).unwrap();
+        }
+        db.flush().unwrap();
+        db.flush_cf(cf1).unwrap();
+
+        for i in 0..1000u32 {
+            assert_eq!(&*db.get(i.to_be_bytes()).unwrap().unwrap(), &[1u8; 128][..]);
+            assert_eq!(
+                &*db.get_cf(cf1, i.to_be_bytes()).unwrap().unwrap(),
+                &[2u8; 128][..]
+            );
+        }
+        // blocks of both column families are cached
+        assert!(cache.get_usage() > 0);
+        assert!(cache.get_usage() <= 16 * 1024 * 1024);
+    }
+}
+
+#[test]
+fn test_full_bloom_filter() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut block_opts = BlockBasedOptions::default();
+        block_opts.set_bloom_filter(10.0, false);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_block_based_table_factory(&block_opts);
+        // the options, and the filter policy with them, may go away early
+        let db = {
+            let opts = opts;
+            DB::open(&opts, &n).unwrap()
+        };
+        drop(block_opts);
+
+        for i in 0..1000u32 {
+            db.put(i.to_be_bytes(), i.to_be_bytes()).unwrap();
+        }
+        db.flush().unwrap();
+
+        assert!(db.key_may_exist(10u32.to_be_bytes(), None));
+        assert!(!db.key_may_exist(b"absent", None));
+        assert_eq!(
+            &*db.get(10u32.to_be_bytes()).unwrap().unwrap(),
+            &10u32.to_be_bytes()
+        );
+        assert!(db.get(b"absent").unwrap().is_none());
+    }
+}
+
+#[test]
+fn test_set_paranoid_file_checks() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_paranoid_file_checks(true);
+        let db = DB::open(&opts, &n).unwrap();
+
+        for i in 0..100u32 {
+            db.put(i.to_be_bytes(), i.to_le_bytes()).unwrap();
+        }
+        db.flush().unwrap();
+        for i in 50..150u32 {
+            db.put(i.to_be_bytes(), i.to_le_bytes()).unwrap();
+        }
+        db.flush().unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        for i in 0..150u32 {
+            assert_eq!(
+                &*db.get(i.to_be_bytes()).unwrap().unwrap(),
+                &i.to_le_bytes()
+            );
+        }
+    }
+}
+
+#[test]
+fn test_ribbon_filter() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut block_opts = BlockBasedOptions::default();
+        block_opts.set_bloom_filter(10.0, false);
+        // the last filter policy set wins
+        block_opts.set_ribbon_filter(10.0);
+
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_block_based_table_factory(&block_opts);
+        let db = DB::open(&opts, &n).unwrap();
+
+        for i in 0..1000u32 {
+            db.put(i.to_be_bytes(), i.to_le_bytes()).unwrap();
+        }
+        db.flush().unwrap();
+
+        for i in 0..1000u32 {
+            assert_eq!(
+                &*db.get(i.to_be_bytes()).unwrap().unwrap(),
+                &i.to_le_bytes()
+            );
+        }
+        assert!(db.get(b"absent").unwrap().is_none());
+        assert!(!db.key_may_exist(b"absent", None));
+    }
+}
+
+#[test]
+#[cfg(all(feature = "lz4", feature = "zstd"))]
+fn test_set_compression_per_level() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compression_per_level(&[
+            DBCompressionType::None,
+            DBCompressionType::None,
+            DBCompressionType::Lz4,
+            DBCompressionType::Lz4,
+            DBCompressionType::Zstd,
+            DBCompressionType::Zstd,
+            DBCompressionType::Zstd,
+        ]);
+        let db = DB::open(&opts, &n).unwrap();
+
+        for i in 0..1000u32 {
+            db.put(i.to_be_bytes(), [i as u8; 64]).unwrap();
+        }
+        db.flush().unwrap();
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        for i in 0..1000u32 {
+            assert_eq!(
+                &*db.get(i.to_be_bytes()).unwrap().unwrap(),
+                &[i as u8; 64][..]
+            );
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "zstd")]
+fn test_bottommost_zstd_dictionary() {
+    let n = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_compression_type(DBCompressionType::None);
+        opts.set_bottommost_compression_type(DBCompressionType::Zstd);
+        opts.set_bottommost_compression_options(-14, 3, 0, 4 * 1024, true);
+        opts.set_bottommost_zstd_max_train_bytes(64 * 1024, true);
+        opts.set_target_file_size_base(64 * 1024);
+        let db = DB::open(&opts, &n).unwrap();
+
+        for round in 0..4u32 {
+            for i in 0..2000u32 {
+                let key = (round * 2000 + i).to_be_bytes();
+                db.put(key, format!("value-{}-{}", round, i).repeat(4))
+                    .unwrap();
+            }
+            db.flush().unwrap();
+        }
+        db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+        for round in 0..4u32 {
+            for i in (0..2000u32).step_by(97) {
+                let key = (round * 2000 + i).to_be_bytes();
+                assert_eq!(
+                    db.get(key).unwrap().unwrap().to_utf8().unwrap(),
+                    format!("value-{}-{}", round, i).repeat(4)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_set_info_log_callback() {
+    use crate::rocksdb::LogLevel;
+    use std::sync::{Arc, Mutex};
+
+    let path = TemporaryDBPath::new();
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let captured = lines.clone();
+        opts.set_info_log_callback(LogLevel::Info, move |level, msg| {
+            captured.lock().unwrap().push((level, msg.to_owned()));
+        });
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.flush().unwrap();
+    }
+    assert!(!lines.lock().unwrap().is_empty());
+    assert!(
+        lines
+            .lock()
+            .unwrap()
+            .iter()
+            .all(|(level, _)| *level as i32 >= LogLevel::Info as i32)
+    );
+    // the callback was dropped together with the options and the database
+    assert_eq!(Arc::strong_count(&lines), 1);
+
+    // nothing is logged at fatal level while opening and writing
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    {
+        let mut opts = Options::default();
+        let captured = lines.clone();
+        opts.set_info_log_callback(LogLevel::Fatal, move |_, msg| {
+            captured.lock().unwrap().push(msg.to_owned());
+        });
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k2", b"v2").unwrap();
+    }
+    assert!(lines.lock().unwrap().is_empty());
+
+    // a later set_log_level applies to the installed callback
+    let lines = Arc::new(Mutex::new(Vec::new()));
+    {
+        let mut opts = Options::default();
+        let captured = lines.clone();
+        opts.set_info_log_callback(LogLevel::Debug, move |_, msg| {
+            captured.lock().unwrap().push(msg.to_owned());
+        });
+        opts.set_log_level(LogLevel::Fatal);
+        let db = DB::open(&opts, &path).unwrap();
+        db.put(b"k3", b"v3").unwrap();
+    }
+    assert!(lines.lock().unwrap().is_empty());
+    assert_eq!(Arc::strong_count(&lines), 1);
+}
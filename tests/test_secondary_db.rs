@@ -0,0 +1,47 @@
+extern crate ckb_rocksdb as rocksdb;
+
+use crate::rocksdb::{SecondaryDB, TemporaryDBPath, prelude::*};
+
+#[test]
+fn secondary_catches_up_with_primary() {
+    let primary_path = TemporaryDBPath::new();
+    let secondary_path = TemporaryDBPath::new();
+
+    let db = DB::open_default(&primary_path).unwrap();
+    db.put(b"k0", b"v0").unwrap();
+
+    let mut opts = Options::default();
+    opts.set_max_open_files(-1);
+    let secondary = SecondaryDB::open_as_secondary(&opts, &primary_path, &secondary_path).unwrap();
+    assert_eq!(&*secondary.get(b"k0").unwrap().unwrap(), b"v0");
+
+    db.put(b"k1", b"v1").unwrap();
+    assert!(secondary.get(b"k1").unwrap().is_none());
+
+    secondary.try_catch_up_with_primary().unwrap();
+    assert_eq!(&*secondary.get(b"k1").unwrap().unwrap(), b"v1");
+}
+
+#[test]
+fn secondary_catches_up_with_primary_cf() {
+    let primary_path = TemporaryDBPath::new();
+    let secondary_path = TemporaryDBPath::new();
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(&opts, &primary_path, ["cf1"]).unwrap();
+
+    let mut opts = Options::default();
+    opts.set_max_open_files(-1);
+    let secondary =
+        SecondaryDB::open_cf_as_secondary(&opts, &primary_path, &secondary_path, ["cf1"]).unwrap();
+
+    db.put_cf(db.cf_handle("cf1").unwrap(), b"k1", b"v1")
+        .unwrap();
+    let cf1 = secondary.cf_handle("cf1").unwrap();
+    assert!(secondary.get_cf(cf1, b"k1").unwrap().is_none());
+
+    secondary.try_catch_up_with_primary().unwrap();
+    assert_eq!(&*secondary.get_cf(cf1, b"k1").unwrap().unwrap(), b"v1");
+}
@@ -81,3 +81,98 @@ fn test_no_in_domain() {
         );
     }
 }
+
+#[test]
+fn test_prefix_same_as_start() {
+    let db_path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_prefix_extractor(SliceTransform::create_fixed_prefix(3));
+
+        let db = DB::open(&opts, &db_path).unwrap();
+        for key in [b"aaa1", b"aaa2", b"bbb1", b"bbb2"] {
+            db.put(key, key).unwrap();
+        }
+
+        let mut readopts = ReadOptions::default();
+        readopts.set_prefix_same_as_start(true);
+        let mut iter = db.get_raw_iter(&readopts);
+        iter.seek(b"aaa");
+        assert_eq!(iter.key(), Some(&b"aaa1"[..]));
+        iter.next();
+        assert_eq!(iter.key(), Some(&b"aaa2"[..]));
+        iter.next();
+        // stops at the prefix edge instead of moving on to bbb1
+        assert!(!iter.valid());
+
+        let mut readopts = ReadOptions::default();
+        readopts.set_total_order_seek(true);
+        let mut iter = db.get_raw_iter(&readopts);
+        iter.seek(b"aaa2");
+        iter.next();
+        assert_eq!(iter.key(), Some(&b"bbb1"[..]));
+    }
+}
+
+#[test]
+fn test_total_order_seek_full_scan() {
+    let db_path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_prefix_extractor(SliceTransform::create_fixed_prefix(3));
+        opts.set_memtable_prefix_bloom_ratio(0.1);
+
+        let db = DB::open(&opts, &db_path).unwrap();
+        let keys = [&b"aaa1"[..], b"aaa2", b"bbb1", b"ccc1", b"ccc2", b"ddd1"];
+        for key in &keys[..3] {
+            db.put(key, key).unwrap();
+        }
+        db.flush().unwrap();
+        for key in &keys[3..] {
+            db.put(key, key).unwrap();
+        }
+
+        let mut readopts = ReadOptions::default();
+        readopts.set_total_order_seek(true);
+        readopts.set_readahead_size(2 * 1024 * 1024);
+        let mut iter = db.get_raw_iter(&readopts);
+        let mut scanned = Vec::new();
+        iter.seek_to_first();
+        while iter.valid() {
+            scanned.push(iter.key().unwrap().to_vec());
+            iter.next();
+        }
+        assert_eq!(scanned, keys.iter().map(|k| k.to_vec()).collect::<Vec<_>>());
+
+        // seeking to a prefix with no keys lands on the next prefix
+        iter.seek(b"bbb2");
+        assert_eq!(iter.key(), Some(&b"ccc1"[..]));
+    }
+}
+
+#[test]
+fn test_capped_prefix_extractor() {
+    let db_path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.set_prefix_extractor(SliceTransform::create_capped_prefix(3));
+
+        let db = DB::open(&opts, &db_path).unwrap();
+        for key in [&b"a"[..], b"aa", b"aaa1", b"aaa2", b"bbb1"] {
+            db.put(key, key).unwrap();
+        }
+
+        let keys: Vec<_> = db.prefix_iterator(b"aaa").map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![key(b"aaa1"), key(b"aaa2")]);
+        // keys shorter than the cap are their own prefix
+        let keys: Vec<_> = db.prefix_iterator(b"aa").map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![key(b"aa")]);
+    }
+}
+
+fn key(k: &[u8]) -> Box<[u8]> {
+    k.to_vec().into_boxed_slice()
+}
@@ -1,7 +1,7 @@
 extern crate ckb_rocksdb as rocksdb;
 
 use crate::rocksdb::{TemporaryDBPath, prelude::*};
-use rocksdb::SstFileWriter;
+use rocksdb::{Env, IngestExternalFileOptions, SstFileWriter};
 
 #[test]
 fn sst_file_writer_works() {
@@ -35,3 +35,81 @@ fn sst_file_writer_works() {
         assert!(db.get(b"k3").unwrap().is_none());
     }
 }
+
+#[test]
+fn sst_file_ingest_cf() {
+    let path = TemporaryDBPath::new();
+    let dir = tempfile::Builder::new()
+        .prefix("_rust_rocksdb_sstfileingestcftest")
+        .tempdir()
+        .expect("Failed to create temporary path for file writer.");
+
+    let writer_path = dir.path().join("filewriter");
+    {
+        let opts = Options::default();
+        let mut writer = SstFileWriter::create(&opts);
+        writer.open(&writer_path).unwrap();
+        writer.put(b"k1", b"v1").unwrap();
+        writer.put(b"k2", b"v2").unwrap();
+        writer.finish().unwrap();
+    }
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        let mut ingest_opts = IngestExternalFileOptions::default();
+        ingest_opts.set_move_files(true);
+        ingest_opts.set_allow_global_seqno(true);
+        db.ingest_external_file_cf_opts(cf1, vec![&writer_path], &ingest_opts)
+            .unwrap();
+
+        assert_eq!(db.get_cf(cf1, b"k1").unwrap().unwrap().as_ref(), b"v1");
+        assert_eq!(db.get_cf(cf1, b"k2").unwrap().unwrap().as_ref(), b"v2");
+        assert!(db.get(b"k1").unwrap().is_none());
+        // the file was moved into the DB rather than copied
+        assert!(!writer_path.exists());
+    }
+}
+
+#[test]
+fn sst_bytes_ingest_cf() {
+    let path = TemporaryDBPath::new();
+    let env = Env::mem_env().unwrap();
+
+    let writer_path = "/sst_bytes_ingest/filewriter";
+    let data = {
+        let mut opts = Options::default();
+        opts.set_env(&env);
+        let mut writer = SstFileWriter::create(&opts);
+        writer.open(writer_path).unwrap();
+        writer.put(b"k1", b"v1").unwrap();
+        writer.put(b"k2", b"v2").unwrap();
+        writer.finish().unwrap();
+        env.read_file(writer_path).unwrap()
+    };
+    assert!(!std::path::Path::new(writer_path).exists());
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        opts.set_env(&env);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        let ingest_opts = IngestExternalFileOptions::default();
+        db.ingest_sst_bytes_cf(cf1, &data, "/sst_bytes_ingest", &ingest_opts)
+            .unwrap();
+        assert_eq!(&*db.get_cf(cf1, b"k1").unwrap().unwrap(), b"v1");
+        assert_eq!(&*db.get_cf(cf1, b"k2").unwrap().unwrap(), b"v2");
+        assert!(db.get(b"k1").unwrap().is_none());
+
+        db.ingest_sst_bytes(&data, "/sst_bytes_ingest", &ingest_opts)
+            .unwrap();
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+    // the DB was kept in memory
+    assert!(std::fs::read_dir(&path).unwrap().next().is_none());
+}
@@ -1,7 +1,7 @@
 extern crate ckb_rocksdb as rocksdb;
 
 use crate::rocksdb::{
-    MergeOperands, Options, TemporaryDBPath, TransactionDB, TransactionDBOptions,
+    ErrorKind, MergeOperands, Options, TemporaryDBPath, TransactionDB, TransactionDBOptions,
     TransactionOptions, WriteOptions, prelude::*,
 };
 
@@ -250,6 +250,41 @@ pub fn get_for_update_cf() {
     }
 }
 
+#[test]
+pub fn get_pinned_for_update() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = TransactionDB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+
+        db.put("k1", "v1").unwrap();
+        db.put_cf(cf1, "k1", "v1").unwrap();
+
+        let tran1 = db.transaction_default();
+        let v1 = tran1.get_pinned_for_update("k1").unwrap().unwrap();
+        assert_eq!(&*v1, b"v1");
+        let v1 = tran1.get_pinned_for_update_cf(cf1, "k1").unwrap().unwrap();
+        assert_eq!(&*v1, b"v1");
+        assert!(tran1.get_pinned_for_update("k2").unwrap().is_none());
+
+        // the keys are locked by tran1
+        let write_options = WriteOptions::default();
+        let mut transaction_options = TransactionOptions::new();
+        transaction_options.set_lock_timeout(10);
+        let tran2 = db.transaction(&write_options, &transaction_options);
+        assert!(tran2.put("k1", "v2").is_err());
+        assert!(tran2.put_cf(cf1, "k1", "v2").is_err());
+        assert!(db.put("k1", "v2").is_err());
+
+        tran1.put("k1", "v3").unwrap();
+        tran1.commit().unwrap();
+        assert_eq!(&*db.get("k1").unwrap().unwrap(), b"v3");
+    }
+}
+
 #[test]
 pub fn test_transaction_merge() {
     #[allow(clippy::unnecessary_wraps)]
@@ -298,3 +333,322 @@ pub fn test_transaction_merge() {
         trans2.commit().unwrap();
     }
 }
+
+#[test]
+pub fn get_for_update_no_validate() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = TransactionDB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").expect("column family not exists.");
+
+        let write_options = WriteOptions::default();
+        let mut transaction_options = TransactionOptions::new();
+        transaction_options.set_snapshot(true);
+        let tran1 = db.transaction(&write_options, &transaction_options);
+
+        // written after tran1 took its snapshot
+        db.put("k1", "v1").expect("failed to put k1 v1");
+        db.put_cf(cf1, "k1", "v1").expect("failed to put k1 v1");
+
+        assert!(tran1.get_for_update("k1").is_err());
+        assert!(tran1.get_for_update_cf(cf1, "k1").is_err());
+
+        let v1 = tran1
+            .get_for_update_no_validate("k1")
+            .expect("failed to get for update k1")
+            .expect("k1 is not exists");
+        assert_eq!(&*v1, b"v1");
+        let v1 = tran1
+            .get_for_update_cf_no_validate(cf1, "k1")
+            .expect("failed to get for update k1")
+            .expect("k1 is not exists");
+        assert_eq!(&*v1, b"v1");
+        assert!(tran1.get_for_update_no_validate("k2").unwrap().is_none());
+
+        tran1.put("k1", "v2").expect("failed to put k1 v2");
+        tran1.commit().unwrap();
+        assert_eq!(&*db.get("k1").unwrap().unwrap(), b"v2");
+    }
+}
+
+#[test]
+pub fn multi_get_for_update() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = TransactionDB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").expect("column family not exists.");
+
+        db.put("k1", "v1").expect("failed to put k1 v1");
+        db.put("k2", "v2").expect("failed to put k2 v2");
+        db.put_cf(cf1, "k1", "v1").expect("failed to put k1 v1");
+
+        let tran1 = db.transaction_default();
+        let values = tran1.multi_get_for_update(["k1", "k2", "k3"], true);
+        assert_eq!(values.len(), 3);
+        assert_eq!(&*values[0].as_ref().unwrap().as_ref().unwrap(), b"v1");
+        assert_eq!(&*values[1].as_ref().unwrap().as_ref().unwrap(), b"v2");
+        assert!(values[2].as_ref().unwrap().is_none());
+
+        let values = tran1.multi_get_for_update_cf(cf1, ["k1", "k2"], true);
+        assert_eq!(&*values[0].as_ref().unwrap().as_ref().unwrap(), b"v1");
+        assert!(values[1].as_ref().unwrap().is_none());
+
+        let mut transaction_options = TransactionOptions::new();
+        transaction_options.set_lock_timeout(10);
+        let tran2 = db.transaction(&WriteOptions::default(), &transaction_options);
+        assert!(tran2.put("k1", "v3").is_err());
+        assert!(tran2.put("k2", "v3").is_err());
+        assert!(tran2.put("k3", "v3").is_err());
+        assert!(tran2.put_cf(cf1, "k1", "v3").is_err());
+        tran2.put("k4", "v4").expect("failed to put k4 v4");
+        tran2.commit().unwrap();
+
+        tran1.put("k3", "v3").expect("failed to put k3 v3");
+        tran1.commit().unwrap();
+        assert_eq!(&*db.get("k1").unwrap().unwrap(), b"v1");
+        assert_eq!(&*db.get("k3").unwrap().unwrap(), b"v3");
+    }
+}
+
+#[test]
+pub fn test_transaction_two_phase_commit() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = TransactionDB::open_default(&path).unwrap();
+
+        let tran1 = db.transaction_default();
+        assert_eq!(tran1.get_name(), None);
+        // an unnamed transaction can not be prepared
+        assert!(tran1.prepare().is_err());
+
+        tran1.set_name(b"xid1").unwrap();
+        assert_eq!(tran1.get_name(), Some(b"xid1".to_vec()));
+
+        let tran2 = db.transaction_default();
+        assert!(tran2.set_name(b"xid1").is_err());
+
+        tran1.put(b"k1", b"v1").unwrap();
+        tran1.prepare().unwrap();
+        assert!(db.get(b"k1").unwrap().is_none());
+
+        tran1.commit().unwrap();
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+    }
+}
+
+#[test]
+pub fn test_transaction_recover_prepared() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = TransactionDB::open_default(&path).unwrap();
+        assert!(db.get_prepared_transactions().is_empty());
+
+        let tran1 = db.transaction_default();
+        tran1.set_name(b"xid1").unwrap();
+        tran1.put(b"k1", b"v1").unwrap();
+        tran1.prepare().unwrap();
+        drop(tran1);
+    }
+    {
+        let db = TransactionDB::open_default(&path).unwrap();
+        assert!(db.get(b"k1").unwrap().is_none());
+
+        let prepared = db.get_prepared_transactions();
+        assert_eq!(prepared.len(), 1);
+        assert_eq!(prepared[0].get_name(), Some(b"xid1".to_vec()));
+        prepared[0].commit().unwrap();
+        drop(prepared);
+
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+        assert!(db.get_prepared_transactions().is_empty());
+    }
+}
+
+#[test]
+pub fn test_transaction_recover_prepared_by_name() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = TransactionDB::open_default(&path).unwrap();
+        for (name, key) in [(b"xid1", b"k1"), (b"xid2", b"k2")] {
+            let tran = db.transaction_default();
+            tran.set_name(name).unwrap();
+            tran.put(key, b"v").unwrap();
+            tran.prepare().unwrap();
+        }
+    }
+    {
+        let db = TransactionDB::open_default(&path).unwrap();
+
+        let prepared = db.get_prepared_transactions();
+        assert_eq!(prepared.len(), 2);
+        for tran in prepared {
+            match tran.get_name().as_deref() {
+                Some(b"xid1") => tran.commit().unwrap(),
+                Some(b"xid2") => tran.rollback().unwrap(),
+                name => panic!("unexpected prepared transaction {:?}", name),
+            }
+        }
+
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v");
+        assert!(db.get(b"k2").unwrap().is_none());
+        assert!(db.get_prepared_transactions().is_empty());
+    }
+}
+
+#[test]
+pub fn test_transaction_max_num_locks() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let mut topts = TransactionDBOptions::default();
+        topts.set_max_num_locks(2);
+        topts.set_num_stripes(4);
+
+        let db = TransactionDB::open_with_descriptor(&opts, &path, topts).unwrap();
+
+        let tran1 = db.transaction_default();
+        tran1.put(b"k1", b"v1").unwrap();
+        tran1.put(b"k2", b"v2").unwrap();
+        // relocking an already held key does not count against the limit
+        tran1.put(b"k1", b"v3").unwrap();
+        let err = tran1.put(b"k3", b"v3").unwrap_err();
+        assert!(err.as_ref().contains("max_num_locks"), "{}", err);
+        tran1.commit().unwrap();
+
+        // locks are released on commit
+        let tran2 = db.transaction_default();
+        tran2.put(b"k3", b"v3").unwrap();
+        tran2.commit().unwrap();
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v3");
+        assert_eq!(&*db.get(b"k3").unwrap().unwrap(), b"v3");
+    }
+}
+
+#[test]
+pub fn test_transaction_db_lock_timeouts() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        let mut topts = TransactionDBOptions::default();
+        topts.set_transaction_lock_timeout(10);
+        topts.set_default_lock_timeout(10);
+
+        let db = TransactionDB::open_with_descriptor(&opts, &path, topts).unwrap();
+
+        let tran1 = db.transaction_default();
+        tran1.put(b"k1", b"v1").unwrap();
+
+        // both wait only 10ms for the lock held by tran1
+        let tran2 = db.transaction_default();
+        let err = tran2.put(b"k1", b"v2").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TimedOut, "{}", err);
+        let err = db.put(b"k1", b"v2").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TimedOut, "{}", err);
+
+        tran1.commit().unwrap();
+        db.put(b"k1", b"v2").unwrap();
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v2");
+    }
+}
+
+#[test]
+pub fn test_transaction_lock_timeout_error_kind() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = TransactionDB::open_default(&path).unwrap();
+
+        let tran1 = db.transaction_default();
+        tran1.put(b"k1", b"v1").unwrap();
+
+        let mut transaction_options = TransactionOptions::new();
+        transaction_options.set_lock_timeout(10);
+        let tran2 = db.transaction(&WriteOptions::default(), &transaction_options);
+        let err = tran2.put(b"k1", b"v2").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TimedOut, "{}", err);
+
+        tran1.commit().unwrap();
+        tran2.put(b"k1", b"v2").unwrap();
+        tran2.commit().unwrap();
+    }
+    assert_eq!(
+        Error::new("Resource busy: ".to_owned()).kind(),
+        ErrorKind::Busy
+    );
+    assert_eq!(Error::new("foo".to_owned()).kind(), ErrorKind::Unknown);
+}
+
+#[test]
+pub fn test_transaction_get_for_update_lock_timeout() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = TransactionDB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let tran1 = db.transaction_default();
+        assert_eq!(&*tran1.get_for_update(b"k1").unwrap().unwrap(), b"v1");
+
+        let mut transaction_options = TransactionOptions::new();
+        transaction_options.set_lock_timeout(10);
+        transaction_options.set_deadlock_detect(true);
+        transaction_options.set_expiration(60_000);
+        let tran2 = db.transaction(&WriteOptions::default(), &transaction_options);
+        let err = tran2.get_for_update(b"k1").unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::TimedOut, "{}", err);
+
+        tran1.commit().unwrap();
+        assert_eq!(&*tran2.get_for_update(b"k1").unwrap().unwrap(), b"v1");
+        tran2.commit().unwrap();
+    }
+}
+
+#[test]
+pub fn test_transaction_snapshot_sequence_number() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = TransactionDB::open_default(&path).unwrap();
+        db.put(b"k0", b"v0").unwrap();
+        let before = db.snapshot().sequence_number();
+
+        let write_options = WriteOptions::default();
+        let mut transaction_options = TransactionOptions::new();
+        let trans1 = db.transaction(&write_options, &transaction_options);
+        assert_eq!(trans1.snapshot().sequence_number(), None);
+
+        transaction_options.set_snapshot(true);
+        let trans2 = db.transaction(&write_options, &transaction_options);
+        assert_eq!(trans2.snapshot().sequence_number(), Some(before));
+
+        db.put(b"k1", b"v1").unwrap();
+        assert!(db.snapshot().sequence_number() > before);
+    }
+}
+
+#[test]
+pub fn test_read_options_with_transaction_snapshot() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = TransactionDB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let write_options = WriteOptions::default();
+        let mut transaction_options = TransactionOptions::new();
+        transaction_options.set_snapshot(true);
+        let trans = db.transaction(&write_options, &transaction_options);
+        let snapshot = trans.snapshot();
+
+        let mut readopts = ReadOptions::default();
+        readopts.set_snapshot(&snapshot);
+        db.put(b"k1", b"v2").unwrap();
+
+        assert_eq!(&*db.get_opt(b"k1", &readopts).unwrap().unwrap(), b"v1");
+        assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v2");
+    }
+}
@@ -84,3 +84,41 @@ fn open_ttl_db_cf_with_descriptor_by_columns() {
         assert!(r.unwrap().unwrap().to_utf8().unwrap() == "v1111");
     }
 }
+
+#[test]
+fn ttl_expires_after_compaction() {
+    let path = TemporaryDBPath::new();
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DBWithTTL::open_cf_with_ttl(&opts, &path, ["cf1"], &[-1, 1]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+
+    db.put(b"k1", b"v1").unwrap();
+    db.put_cf(cf1, b"k1", b"v1").unwrap();
+    assert_eq!(&*db.get(b"k1").unwrap().unwrap(), b"v1");
+
+    std::thread::sleep(std::time::Duration::from_secs(2));
+    db.compact_range(None::<&[u8]>, None::<&[u8]>);
+    db.compact_range_cf(cf1, None::<&[u8]>, None::<&[u8]>);
+
+    // only the default column family was opened with a finite ttl
+    assert!(db.get(b"k1").unwrap().is_none());
+    assert_eq!(&*db.get_cf(cf1, b"k1").unwrap().unwrap(), b"v1");
+}
+
+#[test]
+fn open_with_ttl_expires_entries() {
+    let path = TemporaryDBPath::new();
+
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    let db = DBWithTTL::open_with_ttl(&opts, &path, 1).unwrap();
+
+    db.put(b"k1", b"v1").unwrap();
+    std::thread::sleep(std::time::Duration::from_secs(2));
+    db.compact_range(None::<&[u8]>, None::<&[u8]>);
+
+    assert!(db.get(b"k1").unwrap().is_none());
+}
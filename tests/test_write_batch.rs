@@ -14,7 +14,10 @@
 //
 extern crate ckb_rocksdb as rocksdb;
 
-use crate::rocksdb::WriteBatch;
+use crate::rocksdb::{
+    CfWriteBatch, DBRawIterator, TemporaryDBPath, WriteBatch, WriteBatchIterator,
+    WriteBatchWithIndex, prelude::*,
+};
 
 #[test]
 fn test_write_batch_clear() {
@@ -25,3 +28,269 @@ fn test_write_batch_clear() {
     assert_eq!(batch.len(), 0);
     assert!(batch.is_empty());
 }
+
+#[test]
+fn test_write_batch_delete_range_cf() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+    let cf = db.cf_handle("cf1").unwrap();
+
+    for key in b'a'..=b'z' {
+        db.put_cf(cf, [key], [key]).unwrap();
+        db.put([key], [key]).unwrap();
+    }
+
+    let mut batch = WriteBatch::default();
+    let size = batch.size_in_bytes();
+    batch.delete_range_cf(cf, b"c", b"f").unwrap();
+    assert_eq!(batch.len(), 1);
+    assert!(batch.size_in_bytes() > size);
+    db.write(&batch).unwrap();
+
+    for key in [b"c", b"d", b"e"] {
+        assert!(db.get_cf(cf, key).unwrap().is_none());
+        assert!(db.get(key).unwrap().is_some());
+    }
+    for key in [b"b", b"f"] {
+        assert!(db.get_cf(cf, key).unwrap().is_some());
+    }
+
+    let mut batch = WriteBatch::default();
+    batch.delete_range(b"c", b"f").unwrap();
+    db.write(&batch).unwrap();
+
+    for key in [b"c", b"d", b"e"] {
+        assert!(db.get(key).unwrap().is_none());
+    }
+    for key in [b"b", b"f"] {
+        assert!(db.get(key).unwrap().is_some());
+    }
+}
+
+#[test]
+fn test_cf_write_batch() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+    db.put_cf(cf1, b"k4", b"v4").unwrap();
+
+    let mut batch = CfWriteBatch::new(cf1);
+    batch.put(b"k1", b"v1").unwrap();
+    batch.put(b"k2", b"v2").unwrap();
+    batch.put(b"k3", b"v3").unwrap();
+    batch.delete(b"k4").unwrap();
+    assert_eq!(batch.len(), 4);
+    db.write(&batch).unwrap();
+
+    for (key, value) in [(b"k1", b"v1"), (b"k2", b"v2"), (b"k3", b"v3")] {
+        assert_eq!(db.get_cf(cf1, key).unwrap().unwrap().as_ref(), value);
+        assert!(db.get(key).unwrap().is_none());
+    }
+    assert!(db.get_cf(cf1, b"k4").unwrap().is_none());
+}
+
+#[derive(Default)]
+struct Recorder {
+    ops: Vec<(&'static str, Vec<u8>, Option<Vec<u8>>)>,
+}
+
+impl WriteBatchIterator for Recorder {
+    fn put(&mut self, key: &[u8], value: &[u8]) {
+        self.ops.push(("put", key.to_vec(), Some(value.to_vec())));
+    }
+
+    fn delete(&mut self, key: &[u8]) {
+        self.ops.push(("delete", key.to_vec(), None));
+    }
+}
+
+#[test]
+fn test_write_batch_iterate() {
+    let mut batch = WriteBatch::default();
+    batch.put(b"k1", b"v1").unwrap();
+    batch.put(b"k2", b"v2").unwrap();
+    batch.delete(b"k1").unwrap();
+
+    let mut recorder = Recorder::default();
+    batch.iterate(&mut recorder).unwrap();
+    assert_eq!(
+        recorder.ops,
+        vec![
+            ("put", b"k1".to_vec(), Some(b"v1".to_vec())),
+            ("put", b"k2".to_vec(), Some(b"v2".to_vec())),
+            ("delete", b"k1".to_vec(), None),
+        ]
+    );
+}
+
+#[derive(Default)]
+struct CfRecorder {
+    ops: Vec<(&'static str, u32, Vec<u8>)>,
+}
+
+impl WriteBatchIterator for CfRecorder {
+    fn put(&mut self, _: &[u8], _: &[u8]) {
+        unreachable!()
+    }
+
+    fn delete(&mut self, _: &[u8]) {
+        unreachable!()
+    }
+
+    fn put_cf(&mut self, column_family_id: u32, key: &[u8], _: &[u8]) {
+        self.ops.push(("put", column_family_id, key.to_vec()));
+    }
+
+    fn delete_cf(&mut self, column_family_id: u32, key: &[u8]) {
+        self.ops.push(("delete", column_family_id, key.to_vec()));
+    }
+
+    fn merge_cf(&mut self, column_family_id: u32, key: &[u8], _: &[u8]) {
+        self.ops.push(("merge", column_family_id, key.to_vec()));
+    }
+}
+
+#[test]
+fn test_write_batch_iterate_cf() {
+    let path = TemporaryDBPath::new();
+    let mut opts = Options::default();
+    opts.create_if_missing(true);
+    opts.create_missing_column_families(true);
+    let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+    let cf1 = db.cf_handle("cf1").unwrap();
+    assert_ne!(cf1.id(), 0);
+
+    let mut batch = WriteBatch::default();
+    batch.put(b"k1", b"v1").unwrap();
+    batch.put_cf(cf1, b"k2", b"v2").unwrap();
+    batch.merge_cf(cf1, b"k3", b"v3").unwrap();
+    batch.delete_cf(cf1, b"k2").unwrap();
+
+    let mut recorder = CfRecorder::default();
+    batch.iterate(&mut recorder).unwrap();
+    assert_eq!(
+        recorder.ops,
+        vec![
+            ("put", 0, b"k1".to_vec()),
+            ("put", cf1.id(), b"k2".to_vec()),
+            ("merge", cf1.id(), b"k3".to_vec()),
+            ("delete", cf1.id(), b"k2".to_vec()),
+        ]
+    );
+
+    // the default handler ignores the other column families
+    let mut recorder = Recorder::default();
+    batch.iterate(&mut recorder).unwrap();
+    assert_eq!(
+        recorder.ops,
+        vec![("put", b"k1".to_vec(), Some(b"v1".to_vec()))]
+    );
+
+    // a range delete can't be reported and stops the iteration
+    batch.delete_range(b"k1", b"k2").unwrap();
+    batch.put(b"k4", b"v4").unwrap();
+    let mut recorder = CfRecorder::default();
+    assert!(batch.iterate(&mut recorder).is_err());
+    assert_eq!(recorder.ops.len(), 4);
+}
+
+#[test]
+fn test_write_batch_with_index() {
+    let path = TemporaryDBPath::new();
+    {
+        let opts = Options::default();
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+        db.put(b"k3", b"v3").unwrap();
+
+        let mut batch = WriteBatchWithIndex::default();
+        batch.put(b"k2", b"v2").unwrap();
+        batch.delete(b"k3").unwrap();
+        assert_eq!(batch.len(), 2);
+
+        // staged writes are visible in the batch before it is written
+        assert_eq!(
+            &*batch.get_from_batch(&opts, b"k2").unwrap().unwrap(),
+            b"v2"
+        );
+        assert!(batch.get_from_batch(&opts, b"k1").unwrap().is_none());
+        assert!(db.get(b"k2").unwrap().is_none());
+
+        // and take precedence over the DB in the merged view
+        let get = |key: &[u8]| batch.get_from_batch_and_db(&db, None, key).unwrap();
+        assert_eq!(&*get(b"k1").unwrap(), b"v1");
+        assert_eq!(&*get(b"k2").unwrap(), b"v2");
+        assert!(get(b"k3").is_none());
+
+        db.write_wbwi(&batch).unwrap();
+        assert_eq!(&*db.get(b"k2").unwrap().unwrap(), b"v2");
+        assert!(db.get(b"k3").unwrap().is_none());
+    }
+}
+
+fn collect_keys(mut iter: DBRawIterator<'_>) -> Vec<Vec<u8>> {
+    let mut keys = Vec::new();
+    iter.seek_to_first();
+    while iter.valid() {
+        keys.push(iter.key().unwrap().to_vec());
+        iter.next();
+    }
+    keys
+}
+
+#[test]
+fn test_write_batch_with_index_iterator_with_base() {
+    let path = TemporaryDBPath::new();
+    {
+        let db = DB::open_default(&path).unwrap();
+        db.put(b"k1", b"v1").unwrap();
+
+        let mut batch = WriteBatchWithIndex::default();
+        batch.put(b"k2", b"v2").unwrap();
+
+        let iter = batch.iterator_with_base(db.raw_iterator());
+        assert_eq!(collect_keys(iter), vec![b"k1".to_vec(), b"k2".to_vec()]);
+        // the DB alone does not see the staged write
+        assert_eq!(collect_keys(db.raw_iterator()), vec![b"k1".to_vec()]);
+    }
+}
+
+#[test]
+fn test_write_batch_with_index_iterator_with_base_cf() {
+    let path = TemporaryDBPath::new();
+    {
+        let mut opts = Options::default();
+        opts.create_if_missing(true);
+        opts.create_missing_column_families(true);
+        let db = DB::open_cf(&opts, &path, ["cf1"]).unwrap();
+        let cf1 = db.cf_handle("cf1").unwrap();
+        db.put_cf(cf1, b"k1", b"v1").unwrap();
+        db.put_cf(cf1, b"k3", b"v3").unwrap();
+
+        let mut batch = WriteBatchWithIndex::default();
+        batch.put_cf(cf1, b"k2", b"v2").unwrap();
+
+        let base = db.raw_iterator_cf(cf1).unwrap();
+        let mut iter = batch.iterator_with_base_cf(base, cf1);
+        iter.seek_to_first();
+        let mut entries = Vec::new();
+        while iter.valid() {
+            entries.push((iter.key().unwrap().to_vec(), iter.value().unwrap().to_vec()));
+            iter.next();
+        }
+        assert_eq!(
+            entries,
+            vec![
+                (b"k1".to_vec(), b"v1".to_vec()),
+                (b"k2".to_vec(), b"v2".to_vec()),
+                (b"k3".to_vec(), b"v3".to_vec()),
+            ]
+        );
+    }
+}